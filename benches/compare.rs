@@ -0,0 +1,109 @@
+//! `axum::Router` (matchit) vs `axum_wayfind::Router` (wayfind) on the
+//! GitHub and Discourse route tables: building the router, matching a path
+//! with the bare matcher, and full dispatch through `Service::call`.
+//!
+//! Run with `cargo bench --bench compare`. Memory use and the regression
+//! check live in `cargo bench --bench regression`.
+
+#![allow(clippy::expect_used)] // Benchmarks panic on setup failure by design.
+#![allow(missing_docs)] // `criterion_main!` generates an undocumented `main`.
+
+#[path = "support/routes.rs"]
+mod routes;
+
+use std::hint::black_box;
+
+use axum::body::Body;
+use criterion::{Criterion, criterion_group, criterion_main};
+use http::Request;
+use tower::{Service, ServiceExt};
+
+use routes::{TABLES, sample_path};
+
+fn bench_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build");
+    for table in TABLES {
+        group.bench_function(format!("{}/axum", table.name), |b| {
+            b.iter(|| routes::axum_router(black_box(table)));
+        });
+        group.bench_function(format!("{}/wayfind", table.name), |b| {
+            b.iter(|| routes::wayfind_router(black_box(table)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_match(c: &mut Criterion) {
+    let mut group = c.benchmark_group("match");
+    for table in TABLES {
+        let paths: Vec<String> = table.routes.iter().map(|t| sample_path(t)).collect();
+
+        let matchit = routes::matchit_matcher(table);
+        group.bench_function(format!("{}/matchit", table.name), |b| {
+            b.iter(|| {
+                for path in &paths {
+                    black_box(matchit.at(black_box(path)).expect("route matches"));
+                }
+            });
+        });
+
+        let wayfind = routes::wayfind_matcher(table);
+        group.bench_function(format!("{}/wayfind", table.name), |b| {
+            b.iter(|| {
+                for path in &paths {
+                    black_box(wayfind.search(black_box(path)).expect("route matches"));
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Dispatch every path through `service`, checking each succeeded.
+async fn dispatch_all<S>(service: &mut S, paths: &[String])
+where
+    S: Service<
+            Request<Body>,
+            Response = axum::response::Response,
+            Error = std::convert::Infallible,
+        >,
+{
+    for path in paths {
+        let request = Request::builder()
+            .uri(path.as_str())
+            .body(Body::empty())
+            .expect("valid request");
+        let response = service
+            .ready()
+            .await
+            .expect("infallible")
+            .call(request)
+            .await
+            .expect("infallible");
+        assert!(response.status().is_success(), "{path}");
+    }
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("tokio runtime");
+    let mut group = c.benchmark_group("dispatch");
+    for table in TABLES {
+        let paths: Vec<String> = table.routes.iter().map(|t| sample_path(t)).collect();
+
+        let mut axum = routes::axum_router(table);
+        group.bench_function(format!("{}/axum", table.name), |b| {
+            b.iter(|| runtime.block_on(dispatch_all(&mut axum, &paths)));
+        });
+
+        let mut wayfind = routes::wayfind_router(table);
+        group.bench_function(format!("{}/wayfind", table.name), |b| {
+            b.iter(|| runtime.block_on(dispatch_all(&mut wayfind, &paths)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_build, bench_match, bench_dispatch);
+criterion_main!(benches);
@@ -0,0 +1,105 @@
+//! Full dispatch through `Service::call`, `axum::Router` vs
+//! `axum_wayfind::Router`, on the same routes and requests.
+//!
+//! Run with `cargo bench --bench dispatch`.
+
+#![allow(clippy::expect_used)] // Benchmarks panic on setup failure by design.
+#![allow(missing_docs)] // `criterion_main!` generates an undocumented `main`.
+
+use axum::{body::Body, extract::Path, routing::get};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use http::Request;
+use tower::{Service, ServiceExt};
+
+/// `(template, request path)` pairs: static, single and multi-parameter
+/// routes, a percent-encoded value and a wildcard.
+const CASES: &[(&str, &str)] = &[
+    ("/", "/"),
+    ("/users/{id}", "/users/42"),
+    (
+        "/repos/{owner}/{repo}/issues/{number}",
+        "/repos/tokio-rs/axum/issues/1",
+    ),
+    ("/search/{query}", "/search/caf%C3%A9%20menu"),
+    ("/static/{*path}", "/static/css/site/main.css"),
+];
+
+fn axum_router() -> axum::Router {
+    let mut router = axum::Router::new();
+    for (template, _) in CASES {
+        router = router.route(template, get(|| async {}));
+    }
+    router.route("/params/{a}/{b}", get(axum_params))
+}
+
+async fn axum_params(Path((a, b)): Path<(String, String)>) -> String {
+    a + &b
+}
+
+fn wayfind_router() -> axum_wayfind::Router {
+    let mut router = axum_wayfind::Router::new();
+    for (template, _) in CASES {
+        router = router.route(template, get(|| async {}));
+    }
+    router.route("/params/{a}/{b}", get(wayfind_params))
+}
+
+async fn wayfind_params(
+    axum_wayfind::extract::Path((a, b)): axum_wayfind::extract::Path<(String, String)>,
+) -> String {
+    a + &b
+}
+
+fn request(path: &str) -> Request<Body> {
+    Request::builder()
+        .uri(path)
+        .body(Body::empty())
+        .expect("valid request")
+}
+
+/// Dispatch `path` through `service` and check it succeeded.
+async fn dispatch<S>(service: &mut S, path: &str)
+where
+    S: Service<
+            Request<Body>,
+            Response = axum::response::Response,
+            Error = std::convert::Infallible,
+        >,
+{
+    let response = service
+        .ready()
+        .await
+        .expect("infallible")
+        .call(request(path))
+        .await
+        .expect("infallible");
+    assert!(response.status().is_success());
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("tokio runtime");
+    let mut group = c.benchmark_group("dispatch");
+
+    let paths = CASES
+        .iter()
+        .map(|(_, path)| *path)
+        .chain(["/params/a%20b/c"]);
+    for path in paths {
+        let mut router = axum_router();
+        group.bench_with_input(BenchmarkId::new("axum", path), path, |b, path| {
+            b.iter(|| runtime.block_on(dispatch(&mut router, path)));
+        });
+
+        let mut router = wayfind_router();
+        group.bench_with_input(BenchmarkId::new("wayfind", path), path, |b, path| {
+            b.iter(|| runtime.block_on(dispatch(&mut router, path)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_dispatch);
+criterion_main!(benches);
@@ -0,0 +1,265 @@
+//! Memory use and a local regression check, `axum::Router` vs
+//! `axum_wayfind::Router` on the GitHub and Discourse route tables.
+//!
+//! Run with `cargo bench --bench regression`. For each table it reports
+//! build time, full dispatch time, the bytes a built router retains and the
+//! allocations per dispatched request, then fails if `axum_wayfind` is
+//! worse than `axum` by more than the allowed ratio. Comparing against
+//! `axum` on the same machine, rather than against recorded timings, keeps
+//! the check meaningful across hardware.
+//!
+//! Override a threshold with its environment variable, e.g.
+//! `AXUM_WAYFIND_MAX_DISPATCH_RATIO=1.1 cargo bench --bench regression`.
+
+#![allow(clippy::expect_used)] // Benchmarks panic on setup failure by design.
+#![allow(unsafe_code)] // A counting global allocator needs `unsafe impl GlobalAlloc`.
+
+#[path = "support/routes.rs"]
+mod routes;
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    process::ExitCode,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use axum::body::Body;
+use http::Request;
+use tower::{Service, ServiceExt};
+
+use routes::{TABLES, Table, sample_path};
+
+// ==============================================================================
+// Counting allocator
+// ==============================================================================
+
+/// Wraps the system allocator, counting live bytes and allocation calls.
+struct Counting;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+// SAFETY: every call is forwarded unchanged to `System`, which upholds the
+// `GlobalAlloc` contract; the counters don't affect the returned memory.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: the caller upholds `alloc`'s contract for `layout`.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        // SAFETY: `ptr` was allocated by `System` with `layout`.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        LIVE_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: `ptr` was allocated by `System` with `layout`, and the
+        // caller upholds `realloc`'s contract for `new_size`.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// The bytes `build`'s result keeps allocated.
+fn retained_bytes<T>(build: impl FnOnce() -> T) -> usize {
+    let before = LIVE_BYTES.load(Ordering::Relaxed);
+    let value = build();
+    let retained = LIVE_BYTES.load(Ordering::Relaxed).saturating_sub(before);
+    drop(black_box(value));
+    retained
+}
+
+// ==============================================================================
+// Measurements
+// ==============================================================================
+
+/// Rounds per timing; the median is reported.
+const ROUNDS: usize = 31;
+
+/// The median time of `ROUNDS` runs of `run`.
+fn median(mut run: impl FnMut()) -> Duration {
+    let mut times: Vec<Duration> = (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .collect();
+    times.sort_unstable();
+    times[ROUNDS / 2]
+}
+
+/// Dispatch every path through `service`, checking each succeeded.
+async fn dispatch_all<S>(service: &mut S, paths: &[String])
+where
+    S: Service<
+            Request<Body>,
+            Response = axum::response::Response,
+            Error = std::convert::Infallible,
+        >,
+{
+    for path in paths {
+        let request = Request::builder()
+            .uri(path.as_str())
+            .body(Body::empty())
+            .expect("valid request");
+        let response = service
+            .ready()
+            .await
+            .expect("infallible")
+            .call(request)
+            .await
+            .expect("infallible");
+        assert!(response.status().is_success(), "{path}");
+    }
+}
+
+/// What was measured for one router on one table.
+struct Report {
+    build: Duration,
+    dispatch: Duration,
+    retained: usize,
+    allocations_per_request: f64,
+}
+
+fn measure<S>(build: impl Fn() -> S, paths: &[String]) -> Report
+where
+    S: Service<
+            Request<Body>,
+            Response = axum::response::Response,
+            Error = std::convert::Infallible,
+        >,
+{
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("tokio runtime");
+
+    let build_time = median(|| drop(black_box(build())));
+    let retained = retained_bytes(&build);
+
+    let mut service = build();
+    // Warm up, so lazily initialised state isn't counted below.
+    runtime.block_on(dispatch_all(&mut service, paths));
+    let dispatch = median(|| runtime.block_on(dispatch_all(&mut service, paths)));
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    runtime.block_on(dispatch_all(&mut service, paths));
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    #[allow(clippy::cast_precision_loss)] // Counts are far below 2^52.
+    let allocations_per_request = allocations as f64 / paths.len() as f64;
+
+    Report {
+        build: build_time,
+        dispatch,
+        retained,
+        allocations_per_request,
+    }
+}
+
+// ==============================================================================
+// Thresholds
+// ==============================================================================
+
+/// A metric compared as `axum_wayfind / axum`, failing above `max`.
+struct Threshold {
+    metric: &'static str,
+    env: &'static str,
+    default: f64,
+}
+
+const THRESHOLDS: [Threshold; 4] = [
+    Threshold {
+        metric: "build time",
+        env: "AXUM_WAYFIND_MAX_BUILD_RATIO",
+        default: 3.0,
+    },
+    Threshold {
+        metric: "dispatch time",
+        env: "AXUM_WAYFIND_MAX_DISPATCH_RATIO",
+        default: 1.25,
+    },
+    Threshold {
+        metric: "retained bytes",
+        env: "AXUM_WAYFIND_MAX_MEMORY_RATIO",
+        default: 3.0,
+    },
+    Threshold {
+        metric: "allocations/request",
+        env: "AXUM_WAYFIND_MAX_ALLOCATION_RATIO",
+        default: 1.5,
+    },
+];
+
+impl Threshold {
+    fn max(&self) -> f64 {
+        std::env::var(self.env)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(self.default)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)] // Byte counts are far below 2^52.
+fn check(table: &Table, axum: &Report, wayfind: &Report) -> bool {
+    let metrics = [
+        (axum.build.as_secs_f64(), wayfind.build.as_secs_f64()),
+        (axum.dispatch.as_secs_f64(), wayfind.dispatch.as_secs_f64()),
+        (axum.retained as f64, wayfind.retained as f64),
+        (
+            axum.allocations_per_request,
+            wayfind.allocations_per_request,
+        ),
+    ];
+
+    println!(
+        "{} ({} routes)\n  {:<20} {:>14} {:>14} {:>8} {:>8}",
+        table.name,
+        table.routes.len(),
+        "metric",
+        "axum",
+        "axum_wayfind",
+        "ratio",
+        "max"
+    );
+    let mut ok = true;
+    for (threshold, (base, ours)) in THRESHOLDS.iter().zip(metrics) {
+        let ratio = ours / base.max(f64::MIN_POSITIVE);
+        let max = threshold.max();
+        let verdict = if ratio <= max { "" } else { "  REGRESSION" };
+        ok &= ratio <= max;
+        println!(
+            "  {:<20} {base:>14.3e} {ours:>14.3e} {ratio:>8.2} {max:>8.2}{verdict}",
+            threshold.metric
+        );
+    }
+    ok
+}
+
+fn main() -> ExitCode {
+    // `cargo bench` passes `--bench`; there are no options to parse.
+    let mut ok = true;
+    for table in TABLES {
+        let paths: Vec<String> = table.routes.iter().map(|t| sample_path(t)).collect();
+        let axum = measure(|| routes::axum_router(table), &paths);
+        let wayfind = measure(|| routes::wayfind_router(table), &paths);
+        ok &= check(table, &axum, &wayfind);
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("axum_wayfind regressed past a threshold; see REGRESSION above");
+        ExitCode::FAILURE
+    }
+}
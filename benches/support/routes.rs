@@ -0,0 +1,312 @@
+// ==============================================================================
+// Route tables shared by the benchmarks
+// ==============================================================================
+//
+// Realistic route sets in Axum syntax: the GitHub REST API (the table
+// routers have long been benchmarked on) and a Discourse forum. Every
+// template is accepted by both matchit and wayfind, so the same table can
+// be loaded into `axum::Router` and `axum_wayfind::Router`.
+
+#![allow(dead_code)] // Each benchmark uses a different subset.
+#![allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module items.
+
+use axum::routing::get;
+
+/// A named route table.
+pub(crate) struct Table {
+    pub name: &'static str,
+    pub routes: &'static [&'static str],
+}
+
+pub(crate) const TABLES: &[Table] = &[
+    Table {
+        name: "github",
+        routes: GITHUB,
+    },
+    Table {
+        name: "discourse",
+        routes: DISCOURSE,
+    },
+];
+
+pub(crate) const GITHUB: &[&str] = &[
+    "/authorizations",
+    "/authorizations/{id}",
+    "/applications/{client_id}/tokens/{access_token}",
+    "/events",
+    "/repos/{owner}/{repo}/events",
+    "/networks/{owner}/{repo}/events",
+    "/orgs/{org}/events",
+    "/users/{user}/received_events",
+    "/users/{user}/received_events/public",
+    "/users/{user}/events",
+    "/users/{user}/events/public",
+    "/users/{user}/events/orgs/{org}",
+    "/feeds",
+    "/notifications",
+    "/repos/{owner}/{repo}/notifications",
+    "/notifications/threads/{id}",
+    "/notifications/threads/{id}/subscription",
+    "/repos/{owner}/{repo}/stargazers",
+    "/users/{user}/starred",
+    "/user/starred",
+    "/user/starred/{owner}/{repo}",
+    "/repos/{owner}/{repo}/subscribers",
+    "/users/{user}/subscriptions",
+    "/user/subscriptions",
+    "/repos/{owner}/{repo}/subscription",
+    "/user/subscriptions/{owner}/{repo}",
+    "/users/{user}/gists",
+    "/gists",
+    "/gists/{id}",
+    "/gists/{id}/star",
+    "/repos/{owner}/{repo}/git/blobs/{sha}",
+    "/repos/{owner}/{repo}/git/commits/{sha}",
+    "/repos/{owner}/{repo}/git/refs",
+    "/repos/{owner}/{repo}/git/tags/{sha}",
+    "/repos/{owner}/{repo}/git/trees/{sha}",
+    "/issues",
+    "/user/issues",
+    "/orgs/{org}/issues",
+    "/repos/{owner}/{repo}/issues",
+    "/repos/{owner}/{repo}/issues/{number}",
+    "/repos/{owner}/{repo}/assignees",
+    "/repos/{owner}/{repo}/assignees/{assignee}",
+    "/repos/{owner}/{repo}/issues/{number}/comments",
+    "/repos/{owner}/{repo}/issues/{number}/events",
+    "/repos/{owner}/{repo}/labels",
+    "/repos/{owner}/{repo}/labels/{name}",
+    "/repos/{owner}/{repo}/issues/{number}/labels",
+    "/repos/{owner}/{repo}/milestones/{number}/labels",
+    "/repos/{owner}/{repo}/milestones",
+    "/repos/{owner}/{repo}/milestones/{number}",
+    "/emojis",
+    "/gitignore/templates",
+    "/gitignore/templates/{name}",
+    "/markdown",
+    "/markdown/raw",
+    "/meta",
+    "/rate_limit",
+    "/users/{user}/orgs",
+    "/user/orgs",
+    "/orgs/{org}",
+    "/orgs/{org}/members",
+    "/orgs/{org}/members/{user}",
+    "/orgs/{org}/public_members",
+    "/orgs/{org}/public_members/{user}",
+    "/orgs/{org}/teams",
+    "/teams/{id}",
+    "/teams/{id}/members",
+    "/teams/{id}/members/{user}",
+    "/teams/{id}/repos",
+    "/teams/{id}/repos/{owner}/{repo}",
+    "/user/teams",
+    "/repos/{owner}/{repo}/pulls",
+    "/repos/{owner}/{repo}/pulls/{number}",
+    "/repos/{owner}/{repo}/pulls/{number}/commits",
+    "/repos/{owner}/{repo}/pulls/{number}/files",
+    "/repos/{owner}/{repo}/pulls/{number}/merge",
+    "/repos/{owner}/{repo}/pulls/{number}/comments",
+    "/user/repos",
+    "/users/{user}/repos",
+    "/orgs/{org}/repos",
+    "/repositories",
+    "/repos/{owner}/{repo}",
+    "/repos/{owner}/{repo}/contributors",
+    "/repos/{owner}/{repo}/languages",
+    "/repos/{owner}/{repo}/teams",
+    "/repos/{owner}/{repo}/tags",
+    "/repos/{owner}/{repo}/branches",
+    "/repos/{owner}/{repo}/branches/{branch}",
+    "/repos/{owner}/{repo}/collaborators",
+    "/repos/{owner}/{repo}/collaborators/{user}",
+    "/repos/{owner}/{repo}/comments",
+    "/repos/{owner}/{repo}/commits/{sha}/comments",
+    "/repos/{owner}/{repo}/comments/{id}",
+    "/repos/{owner}/{repo}/commits",
+    "/repos/{owner}/{repo}/commits/{sha}",
+    "/repos/{owner}/{repo}/readme",
+    "/repos/{owner}/{repo}/contents/{*path}",
+    "/repos/{owner}/{repo}/keys",
+    "/repos/{owner}/{repo}/keys/{id}",
+    "/repos/{owner}/{repo}/downloads",
+    "/repos/{owner}/{repo}/downloads/{id}",
+    "/repos/{owner}/{repo}/forks",
+    "/repos/{owner}/{repo}/hooks",
+    "/repos/{owner}/{repo}/hooks/{id}",
+    "/repos/{owner}/{repo}/releases",
+    "/repos/{owner}/{repo}/releases/{id}",
+    "/repos/{owner}/{repo}/releases/{id}/assets",
+    "/repos/{owner}/{repo}/stats/contributors",
+    "/repos/{owner}/{repo}/stats/commit_activity",
+    "/repos/{owner}/{repo}/stats/code_frequency",
+    "/repos/{owner}/{repo}/stats/participation",
+    "/repos/{owner}/{repo}/stats/punch_card",
+    "/repos/{owner}/{repo}/statuses/{ref}",
+    "/search/repositories",
+    "/search/code",
+    "/search/issues",
+    "/search/users",
+    "/legacy/issues/search/{owner}/{repository}/{state}/{keyword}",
+    "/legacy/repos/search/{keyword}",
+    "/legacy/user/search/{keyword}",
+    "/legacy/user/email/{email}",
+    "/users/{user}",
+    "/user",
+    "/users",
+    "/user/emails",
+    "/users/{user}/followers",
+    "/user/followers",
+    "/users/{user}/following",
+    "/user/following",
+    "/user/following/{user}",
+    "/users/{user}/following/{target_user}",
+    "/users/{user}/keys",
+    "/user/keys",
+    "/user/keys/{id}",
+];
+
+pub(crate) const DISCOURSE: &[&str] = &[
+    "/",
+    "/latest",
+    "/latest.json",
+    "/top",
+    "/top/{period}",
+    "/categories",
+    "/categories.json",
+    "/c/{category_slug}/{category_id}",
+    "/c/{category_slug}/{category_id}/l/latest",
+    "/c/{category_slug}/{category_id}/l/top",
+    "/c/{category_slug}/{category_id}/l/new",
+    "/t/{topic_slug}/{topic_id}",
+    "/t/{topic_slug}/{topic_id}/summary",
+    "/t/{topic_slug}/{topic_id}/{post_number}",
+    "/posts",
+    "/posts/{id}",
+    "/posts/{id}/replies",
+    "/posts/{id}/revisions/{revision}",
+    "/u",
+    "/u/{username}",
+    "/u/{username}/summary",
+    "/u/{username}/activity",
+    "/u/{username}/activity/topics",
+    "/u/{username}/activity/replies",
+    "/u/{username}/badges",
+    "/u/{username}/notifications",
+    "/u/{username}/messages",
+    "/u/{username}/preferences",
+    "/u/{username}/preferences/account",
+    "/u/{username}/preferences/profile",
+    "/u/{username}/preferences/emails",
+    "/u/{username}/preferences/notifications",
+    "/u/{username}/preferences/security",
+    "/tag/{tag_id}",
+    "/tags",
+    "/tags/{tag_id}/l/latest",
+    "/badges",
+    "/badges/{id}/{slug}",
+    "/groups",
+    "/groups/{group}",
+    "/groups/{group}/members",
+    "/groups/{group}/activity",
+    "/search",
+    "/search/query",
+    "/session",
+    "/session/csrf",
+    "/session/{login}",
+    "/about",
+    "/faq",
+    "/tos",
+    "/privacy",
+    "/admin",
+    "/admin/dashboard",
+    "/admin/users/list/{query}",
+    "/admin/users/{id}/{username}",
+    "/admin/site_settings",
+    "/admin/site_settings/category/{category}",
+    "/admin/customize/themes",
+    "/admin/customize/themes/{id}",
+    "/admin/logs/staff_action_logs",
+    "/admin/plugins",
+    "/uploads/{site}/original/{*path}",
+    "/user_avatar/{hostname}/{username}/{size}/{version}",
+    "/letter_avatar/{username}/{size}/{version}",
+    "/highlight-js/{hostname}/{version}",
+    "/stylesheets/{name}",
+    "/manifest.webmanifest",
+    "/srv/status",
+    "/notifications",
+    "/notifications/mark-read",
+    "/bookmarks",
+    "/review",
+    "/review/{reviewable_id}",
+    "/invites",
+    "/invites/{id}",
+    "/my/{*path}",
+];
+
+/// A request path matching `template`: numeric-looking parameters get a
+/// number, others a word, wildcards a few segments.
+pub(crate) fn sample_path(template: &str) -> String {
+    let mut path = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        path.push_str(&rest[..open]);
+        let close = open + rest[open..].find('}').unwrap_or(rest.len() - open);
+        let name = &rest[open + 1..close];
+        path.push_str(if name.starts_with('*') {
+            "assets/images/logo.png"
+        } else if name.ends_with("id") || name.ends_with("number") || name == "size" {
+            "4242"
+        } else {
+            "octocat"
+        });
+        rest = rest.get(close + 1..).unwrap_or_default();
+    }
+    path.push_str(rest);
+    path
+}
+
+/// `template` in wayfind's own syntax, for benchmarking the bare matcher.
+pub(crate) fn wayfind_template(template: &str) -> String {
+    template
+        .replace("{*", "<*")
+        .replace('{', "<")
+        .replace('}', ">")
+}
+
+pub(crate) fn axum_router(table: &Table) -> axum::Router {
+    table
+        .routes
+        .iter()
+        .fold(axum::Router::new(), |router, template| {
+            router.route(template, get(|| async {}))
+        })
+}
+
+pub(crate) fn wayfind_router(table: &Table) -> axum_wayfind::Router {
+    table
+        .routes
+        .iter()
+        .fold(axum_wayfind::Router::new(), |router, template| {
+            router.route(template, get(|| async {}))
+        })
+}
+
+pub(crate) fn matchit_matcher(table: &Table) -> matchit::Router<usize> {
+    let mut router = matchit::Router::new();
+    for (idx, template) in table.routes.iter().enumerate() {
+        router.insert(*template, idx).expect("valid matchit route");
+    }
+    router
+}
+
+pub(crate) fn wayfind_matcher(table: &Table) -> wayfind::Router<usize> {
+    let mut router = wayfind::Router::new();
+    for (idx, template) in table.routes.iter().enumerate() {
+        router
+            .insert(&wayfind_template(template), idx)
+            .expect("valid wayfind route");
+    }
+    router
+}
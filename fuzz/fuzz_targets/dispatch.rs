@@ -0,0 +1,69 @@
+//! Full dispatch through `Router::call` with arbitrary URIs, across static,
+//! parameter, inline, wildcard and nested routes. Nothing may panic, and no
+//! request may end in a 500: every extractor either succeeds or rejects the
+//! request as a client error.
+
+#![no_main]
+
+use std::{collections::HashMap, sync::LazyLock};
+
+use axum::{body::Body, routing::get};
+use axum_wayfind::{
+    Router,
+    extract::{MatchedPath, Path, RawPathParams},
+};
+use http::{Request, StatusCode, Uri};
+use libfuzzer_sys::fuzz_target;
+use tower::ServiceExt;
+
+static ROUTER: LazyLock<Router> = LazyLock::new(|| {
+    let api = Router::new()
+        .route(
+            "/items/{item}",
+            get(|Path(params): Path<HashMap<String, String>>| async move { format!("{params:?}") }),
+        )
+        .fallback(|| async { StatusCode::NOT_FOUND });
+
+    Router::new()
+        .route("/", get(|| async {}))
+        .route(
+            "/users/{id}",
+            get(|Path(id): Path<u32>| async move { id.to_string() }),
+        )
+        .route(
+            "/files/{*path}",
+            get(|Path(path): Path<Vec<String>>| async move { path.join("|") }),
+        )
+        .route(
+            "/docs/{name}.{ext}",
+            get(|Path((name, ext)): Path<(String, String)>| async move { name + &ext }),
+        )
+        .route(
+            "/raw/{*rest}",
+            get(|params: RawPathParams, path: MatchedPath| async move {
+                format!("{} {}", path.as_str(), params.len())
+            }),
+        )
+        .nest("/api/{version}", api)
+        .nest_service("/static", Router::new().route("/{*file}", get(|| async {})))
+});
+
+static RUNTIME: LazyLock<tokio::runtime::Runtime> = LazyLock::new(|| {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("tokio runtime")
+});
+
+fuzz_target!(|input: &[u8]| {
+    let Ok(uri) = Uri::try_from(input) else {
+        return;
+    };
+    let Ok(request) = Request::builder().uri(uri).body(Body::empty()) else {
+        return;
+    };
+
+    let response = RUNTIME
+        .block_on(ROUTER.clone().oneshot(request))
+        .expect("infallible");
+    assert_ne!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+});
@@ -0,0 +1,16 @@
+//! Percent-decoding of path parameters: never panics, and succeeds exactly
+//! when the decoded bytes are valid UTF-8.
+
+#![no_main]
+
+use axum_wayfind::extract::path::PercentDecodedStr;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let bytes: Vec<u8> = percent_encoding::percent_decode_str(input).collect();
+
+    match PercentDecodedStr::new(input) {
+        Ok(decoded) => assert_eq!(decoded.as_bytes(), bytes),
+        Err(_) => assert!(std::str::from_utf8(&bytes).is_err()),
+    }
+});
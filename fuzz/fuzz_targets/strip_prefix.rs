@@ -0,0 +1,23 @@
+//! Prefix stripping for nested routers: arbitrary URIs and prefixes never
+//! panic, and a stripped URI is a suffix of the original path with the
+//! query kept.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, &str)| {
+    let (uri, prefix) = input;
+    let Ok(uri) = uri.parse::<http::Uri>() else {
+        return;
+    };
+
+    if let Some(stripped) = axum_wayfind::__fuzz::strip_prefix(&uri, prefix) {
+        assert!(stripped.path().starts_with('/'));
+        assert!(
+            uri.path()
+                .ends_with(stripped.path().trim_start_matches('/'))
+        );
+        assert_eq!(stripped.query(), uri.query());
+    }
+});
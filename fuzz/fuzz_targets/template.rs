@@ -0,0 +1,23 @@
+//! Axum-style template translation: any string either translates or is
+//! rejected with a typed error, and a translation wayfind accepts inserts
+//! without panicking.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|path: &str| {
+    let Ok(translated) = axum_wayfind::__fuzz::axum_to_wayfind(path) else {
+        return;
+    };
+
+    // Translation only swaps parameter delimiters.
+    assert_eq!(translated.len(), path.len());
+    assert_eq!(
+        translated.replace(['<', '>'], ""),
+        path.replace(['{', '}'], "")
+    );
+
+    let mut router = wayfind::Router::new();
+    drop(router.insert(&translated, ()));
+});
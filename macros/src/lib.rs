@@ -0,0 +1,44 @@
+//! Procedural macros for [`axum-wayfind`](https://docs.rs/axum-wayfind).
+//!
+//! Don't depend on this crate directly: enable `axum-wayfind`'s `macros`
+//! (or `typed-routing`) feature and use the re-exports from `axum_wayfind`.
+
+#![warn(missing_docs)]
+
+mod path_fields;
+mod route;
+// A copy of `axum-wayfind`'s parser, kept identical to it, so templates are
+// parsed exactly as the router parses them.
+#[allow(dead_code)] // Only the parser is needed here, not the wayfind translation.
+mod syntax;
+mod template;
+mod typed_path;
+
+/// Check a route template at compile time and expand to it.
+#[proc_macro]
+pub fn route(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as route::RouteInput);
+    route::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `axum_wayfind::checked::PathFields` for a `Path` struct.
+#[proc_macro_derive(PathFields, attributes(path_fields))]
+pub fn derive_path_fields(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    path_fields::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `axum_wayfind::typed::WayfindTypedPath` for a struct.
+///
+/// See `axum_wayfind::typed::WayfindTypedPath` for usage.
+#[proc_macro_derive(WayfindTypedPath, attributes(typed_path))]
+pub fn derive_wayfind_typed_path(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    typed_path::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,201 @@
+// ==============================================================================
+// #[derive(PathFields)]
+// ==============================================================================
+//
+// Lists the parameter names a `Path<T>` struct reads, as `route!` sees them:
+// serde renames applied, fields serde may leave out (`default`, `skip`,
+// `Option`) omitted, and nested or flattened structs listed through their
+// own `PathFields` impl.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Field, Fields, LitStr, Type, ext::IdentExt as _,
+    meta::ParseNestedMeta,
+};
+
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "`PathFields` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "`PathFields` requires named fields; tuple structs are matched by position",
+        ));
+    };
+
+    let container = SerdeAttrs::parse(&input.attrs)?;
+    reject_rename_all(&container, "PathFields")?;
+
+    let mut entries = Vec::new();
+    for field in &named.named {
+        let serde = SerdeAttrs::parse(&field.attrs)?;
+        let ty = &field.ty;
+
+        if serde.flatten || is_nested(field)? {
+            entries.push(quote! {
+                ::axum_wayfind::checked::PathField::Nested(
+                    <#ty as ::axum_wayfind::checked::PathFields>::FIELDS,
+                )
+            });
+        } else if !(serde.skip || serde.default || container.default || is_option(ty)) {
+            let name = field_name(field, serde.rename.as_ref());
+            entries.push(quote! { ::axum_wayfind::checked::PathField::Param(#name) });
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::axum_wayfind::checked::PathFields for #ident #ty_generics #where_clause {
+            const FIELDS: &'static [::axum_wayfind::checked::PathField] = &[#(#entries),*];
+        }
+    })
+}
+
+/// The parameter name serde reads `field` from, honouring
+/// `#[serde(rename = "...")]`. Also used by `WayfindTypedPath`.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn param_name(field: &Field) -> syn::Result<String> {
+    let serde = SerdeAttrs::parse(&field.attrs)?;
+    Ok(field_name(field, serde.rename.as_ref()))
+}
+
+/// Reject a container-level `#[serde(rename_all = "...")]` on a struct
+/// deriving `derive`, whose parameter names we couldn't follow.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn check_container(attrs: &[Attribute], derive: &str) -> syn::Result<()> {
+    reject_rename_all(&SerdeAttrs::parse(attrs)?, derive)
+}
+
+fn reject_rename_all(container: &SerdeAttrs, derive: &str) -> syn::Result<()> {
+    container.rename_all.as_ref().map_or(Ok(()), |rename_all| {
+        Err(syn::Error::new_spanned(
+            rename_all,
+            format!("`{derive}` doesn't support `rename_all`; rename fields individually"),
+        ))
+    })
+}
+
+/// `rename`, or else the field's identifier.
+fn field_name(field: &Field, rename: Option<&LitStr>) -> String {
+    rename.map_or_else(
+        || {
+            field
+                .ident
+                .as_ref()
+                .map(|ident| ident.unraw().to_string())
+                .unwrap_or_default()
+        },
+        LitStr::value,
+    )
+}
+
+/// Whether the field is marked `#[path_fields(nested)]`.
+fn is_nested(field: &Field) -> syn::Result<bool> {
+    let mut nested = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("path_fields"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nested") {
+                nested = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `nested`"))
+            }
+        })?;
+    }
+    Ok(nested)
+}
+
+/// Whether `ty` is spelled `Option<…>`, which serde fills with `None` when
+/// the parameter is missing.
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Option")
+}
+
+/// The `#[serde(...)]` options that change which parameters a struct reads.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<LitStr>,
+    rename_all: Option<LitStr>,
+    flatten: bool,
+    default: bool,
+    skip: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if let Some(rename) = deserialize_name(&meta)? {
+                        parsed.rename = Some(rename);
+                    }
+                } else if meta.path.is_ident("rename_all") {
+                    if let Some(rename_all) = deserialize_name(&meta)? {
+                        parsed.rename_all = Some(rename_all);
+                    }
+                } else if meta.path.is_ident("flatten") {
+                    parsed.flatten = true;
+                } else if meta.path.is_ident("default") {
+                    parsed.default = true;
+                    if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<LitStr>()?;
+                    }
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    parsed.skip = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Irrelevant here, e.g. `deserialize_with = "..."`.
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    // Irrelevant here, e.g. `bound(...)`.
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    content.parse::<TokenStream>()?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// The name serde deserializes with, from `rename = "..."` or
+/// `rename(serialize = "...", deserialize = "...")`; `None` if only the
+/// serialized name changes.
+fn deserialize_name(meta: &ParseNestedMeta<'_>) -> syn::Result<Option<LitStr>> {
+    if !meta.input.peek(syn::token::Paren) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+
+    let mut name = None;
+    meta.parse_nested_meta(|inner| {
+        let value: LitStr = inner.value()?.parse()?;
+        if inner.path.is_ident("deserialize") {
+            name = Some(value);
+            Ok(())
+        } else if inner.path.is_ident("serialize") {
+            Ok(())
+        } else {
+            Err(inner.error("expected `serialize` or `deserialize`"))
+        }
+    })?;
+    Ok(name)
+}
@@ -0,0 +1,71 @@
+// ==============================================================================
+// route!("...")
+// ==============================================================================
+//
+// Validates a template at compile time and expands to the same string
+// literal. With a `PathFields` type after the template, it also emits a
+// const assertion that every field of the type is a parameter of the
+// template, evaluated by rustc during compilation.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    LitStr, Token, Type,
+    parse::{Parse, ParseStream},
+};
+
+use crate::template;
+
+/// `"/template"` or `"/template", PathType`.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) struct RouteInput {
+    template: LitStr,
+    path_type: Option<Type>,
+}
+
+impl Parse for RouteInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let template = input.parse()?;
+        let mut path_type = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            path_type = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self {
+            template,
+            path_type,
+        })
+    }
+}
+
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn expand(input: &RouteInput) -> syn::Result<TokenStream> {
+    let template = &input.template;
+    let path = template.value();
+    let parts = template::parse(template, &path)?;
+
+    let Some(path_type) = &input.path_type else {
+        return Ok(quote! { #template });
+    };
+
+    let params = template::param_names(&parts);
+    // Braces are escaped: the message is a format string.
+    let message = format!(
+        "a field of `{}` is not a parameter of `{path}`",
+        quote!(#path_type).to_string().replace(' ', "")
+    )
+    .replace('{', "{{")
+    .replace('}', "}}");
+
+    Ok(quote! {
+        {
+            const _: () = if !::axum_wayfind::__private::fields_are_params(
+                <#path_type as ::axum_wayfind::checked::PathFields>::FIELDS,
+                &[#(#params),*],
+            ) {
+                ::core::panic!(#message);
+            };
+            #template
+        }
+    })
+}
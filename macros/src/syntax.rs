@@ -0,0 +1,251 @@
+// ==============================================================================
+// Syntax Translation: Axum → wayfind
+// ==============================================================================
+//
+// Axum uses `{param}` and `{*wildcard}` for path parameters, while wayfind
+// uses `<param>` and `<*wildcard>`. We translate at route-insertion time so
+// users write Axum-style paths and the wayfind engine receives its native
+// syntax.
+//
+// `axum-wayfind-macros` keeps an identical copy in `macros/src/syntax.rs`,
+// so templates checked at compile time are parsed exactly as the router
+// parses them; an integration test fails if the two drift apart. Keep it
+// free of dependencies on the rest of the crate.
+
+/// A piece of a parsed Axum-style path template.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Part<'a> {
+    /// Literal text, matched as-is.
+    Static(&'a str),
+    /// `{name}`.
+    Param(&'a str),
+    /// `{*name}`.
+    Wildcard(&'a str),
+}
+
+/// Why a path template failed to parse.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TemplateError {
+    /// A `{` without a matching `}`.
+    Unclosed,
+    /// A `}` without a preceding `{`.
+    UnmatchedClose,
+    /// `{}`.
+    EmptyName,
+    /// `{*}`.
+    UnnamedWildcard,
+    /// `<` or `>`, which wayfind would read as its own parameter syntax.
+    AngleBracket,
+}
+
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Unclosed => "unclosed `{` in path template",
+            Self::UnmatchedClose => "unmatched `}` in path template",
+            Self::EmptyName => "empty parameter name in path template",
+            Self::UnnamedWildcard => "wildcard `*` without a name in path template",
+            Self::AngleBracket => "`<` or `>` in path template (reserved by wayfind)",
+        })
+    }
+}
+
+/// Splits an Axum-style path template into static text, parameters and
+/// wildcards.
+///
+/// Parameters may appear anywhere wayfind accepts them, including inline
+/// (`/{name}.{ext}`) and mid-path wildcards (`/{*path}/edit`). This is the
+/// single source of truth for template syntax: [`axum_to_wayfind`] and the
+/// `axum-wayfind-macros` crate both use it.
+///
+/// # Errors
+///
+/// Returns the first syntax error in the template.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn parse(path: &str) -> Result<Vec<Part<'_>>, TemplateError> {
+    let mut parts = Vec::new();
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        let Some(open) = rest.find(['{', '}']) else {
+            parts.push(static_part(rest)?);
+            break;
+        };
+        if rest[open..].starts_with('}') {
+            return Err(TemplateError::UnmatchedClose);
+        }
+        if open > 0 {
+            parts.push(static_part(&rest[..open])?);
+        }
+
+        let after = &rest[open + 1..];
+        let close = after.find('}').ok_or(TemplateError::Unclosed)?;
+        let name = &after[..close];
+        if name.is_empty() {
+            return Err(TemplateError::EmptyName);
+        }
+        if name.contains(['<', '>']) {
+            return Err(TemplateError::AngleBracket);
+        }
+        parts.push(match name.strip_prefix('*') {
+            Some("") => return Err(TemplateError::UnnamedWildcard),
+            Some(wildcard) => Part::Wildcard(wildcard),
+            None => Part::Param(name),
+        });
+        rest = &after[close + 1..];
+    }
+
+    Ok(parts)
+}
+
+/// Literal text, unless it contains characters wayfind would misread.
+fn static_part(text: &str) -> Result<Part<'_>, TemplateError> {
+    if text.contains(['<', '>']) {
+        return Err(TemplateError::AngleBracket);
+    }
+    Ok(Part::Static(text))
+}
+
+/// Translates an Axum-style path template to wayfind syntax.
+///
+/// - `{name}` → `<name>`
+/// - `{*name}` → `<*name>`
+///
+/// Static segments and leading `/` are preserved as-is.
+///
+/// # Panics
+///
+/// Panics if the template doesn't parse; see [`try_axum_to_wayfind`].
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+#[allow(clippy::panic)] // Intentional: invalid path syntax is a programming error.
+pub(crate) fn axum_to_wayfind(path: &str) -> String {
+    try_axum_to_wayfind(path).unwrap_or_else(|err| panic!("{err}: `{path}`"))
+}
+
+/// Translates an Axum-style path template to wayfind syntax, reporting
+/// syntax errors instead of panicking.
+///
+/// # Errors
+///
+/// Returns the first syntax error in the template: an unclosed `{`, an
+/// unmatched `}`, an empty or unnamed parameter, or a `<` / `>`.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn try_axum_to_wayfind(path: &str) -> Result<String, TemplateError> {
+    let parts = parse(path)?;
+
+    let mut result = String::with_capacity(path.len());
+    for part in parts {
+        match part {
+            Part::Static(text) => result.push_str(text),
+            Part::Param(name) => {
+                result.push('<');
+                result.push_str(name);
+                result.push('>');
+            }
+            Part::Wildcard(name) => {
+                result.push_str("<*");
+                result.push_str(name);
+                result.push('>');
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_path() {
+        assert_eq!(axum_to_wayfind("/hello/world"), "/hello/world");
+    }
+
+    #[test]
+    fn single_param() {
+        assert_eq!(axum_to_wayfind("/users/{id}"), "/users/<id>");
+    }
+
+    #[test]
+    fn multiple_params() {
+        assert_eq!(
+            axum_to_wayfind("/users/{user_id}/posts/{post_id}"),
+            "/users/<user_id>/posts/<post_id>"
+        );
+    }
+
+    #[test]
+    fn wildcard() {
+        assert_eq!(axum_to_wayfind("/files/{*path}"), "/files/<*path>");
+    }
+
+    #[test]
+    fn root() {
+        assert_eq!(axum_to_wayfind("/"), "/");
+    }
+
+    #[test]
+    fn no_params() {
+        assert_eq!(axum_to_wayfind("/static/page"), "/static/page");
+    }
+
+    #[test]
+    fn inline_params_and_mid_path_wildcard() {
+        assert_eq!(
+            axum_to_wayfind("/{*path}/files/{name}.{ext}"),
+            "/<*path>/files/<name>.<ext>"
+        );
+        assert_eq!(
+            parse("/{*path}/files/{name}.{ext}"),
+            Ok(vec![
+                Part::Static("/"),
+                Part::Wildcard("path"),
+                Part::Static("/files/"),
+                Part::Param("name"),
+                Part::Static("."),
+                Part::Param("ext"),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_reports_errors() {
+        assert_eq!(parse("/users/{id"), Err(TemplateError::Unclosed));
+        assert_eq!(parse("/users/id}"), Err(TemplateError::UnmatchedClose));
+        assert_eq!(parse("/users/{}"), Err(TemplateError::EmptyName));
+        assert_eq!(parse("/files/{*}"), Err(TemplateError::UnnamedWildcard));
+        assert_eq!(parse("/a<b>"), Err(TemplateError::AngleBracket));
+        assert_eq!(parse("/{a<b>}"), Err(TemplateError::AngleBracket));
+        assert_eq!(
+            try_axum_to_wayfind("/users/{id"),
+            Err(TemplateError::Unclosed)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unclosed `{` in path template")]
+    fn unclosed_brace_panics() {
+        axum_to_wayfind("/users/{id");
+    }
+
+    #[test]
+    #[should_panic(expected = "unmatched `}` in path template")]
+    fn unmatched_close_brace_panics() {
+        axum_to_wayfind("/users/id}");
+    }
+
+    #[test]
+    #[should_panic(expected = "empty parameter name in path template")]
+    fn empty_param_name_panics() {
+        axum_to_wayfind("/users/{}");
+    }
+
+    #[test]
+    #[should_panic(expected = "wildcard `*` without a name in path template")]
+    fn wildcard_without_name_panics() {
+        axum_to_wayfind("/files/{*}");
+    }
+}
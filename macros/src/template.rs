@@ -0,0 +1,56 @@
+// ==============================================================================
+// Route template validation
+// ==============================================================================
+//
+// The checks `Router::route` applies to a template, run at compile time on a
+// string literal: a leading `/`, the shared parser, unique parameter names
+// and the reserved `__private_` prefix.
+
+use syn::LitStr;
+
+use crate::syntax::{self, Part};
+
+/// Parse the template `path`, the value of `lit`, reporting problems at
+/// `lit`.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn parse<'a>(lit: &LitStr, path: &'a str) -> syn::Result<Vec<Part<'a>>> {
+    if !path.starts_with('/') {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("path must start with `/`, got `{path}`"),
+        ));
+    }
+
+    let parts = syntax::parse(path)
+        .map_err(|err| syn::Error::new(lit.span(), format!("{err}: `{path}`")))?;
+
+    let params = param_names(&parts);
+    for (idx, name) in params.iter().enumerate() {
+        if name.starts_with("__private_") {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("parameter names starting with `__private_` are reserved: `{path}`"),
+            ));
+        }
+        if params[..idx].contains(name) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("duplicate parameter `{name}` in `{path}`"),
+            ));
+        }
+    }
+
+    Ok(parts)
+}
+
+/// The parameter names of `parts`, in template order.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn param_names<'a>(parts: &[Part<'a>]) -> Vec<&'a str> {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Param(name) | Part::Wildcard(name) => Some(*name),
+            Part::Static(_) => None,
+        })
+        .collect()
+}
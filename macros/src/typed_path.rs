@@ -0,0 +1,240 @@
+// ==============================================================================
+// #[derive(WayfindTypedPath)]
+// ==============================================================================
+//
+// Reads the template from `#[typed_path("...")]`, checks it against the
+// struct's fields and its static text against what a URI path allows, and
+// generates:
+//
+// - `WayfindTypedPath` with the template as `PATH`,
+// - `Display`, rendering the URL with each value percent-encoded,
+// - `FromRequestParts`, deserializing through `axum_wayfind::extract::Path`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Fields, Ident, Index, LitStr, spanned::Spanned as _,
+};
+
+use crate::{path_fields, syntax::Part, template};
+
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`WayfindTypedPath` cannot be derived for generic types",
+        ));
+    }
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "`WayfindTypedPath` can only be derived for structs",
+        ));
+    };
+
+    let template = template_attr(input)?;
+    let path = template.value();
+    let parts = template::parse(&template, &path)?;
+    let params = template::param_names(&parts);
+    for part in &parts {
+        if let Part::Static(text) = part {
+            check_uri_text(&template, text)?;
+        }
+    }
+
+    path_fields::check_container(&input.attrs, "WayfindTypedPath")?;
+    let fields = FieldAccess::new(&data.fields, &params, &template)?;
+
+    let mut position = 0;
+    let mut writes = Vec::with_capacity(parts.len());
+    for part in &parts {
+        writes.push(match part {
+            Part::Static(text) => quote! { f.write_str(#text)?; },
+            Part::Param(name) => {
+                let field = fields.access(name, position);
+                position += 1;
+                quote! { ::axum_wayfind::__private::write_segment(f, &#field)?; }
+            }
+            Part::Wildcard(name) => {
+                let field = fields.access(name, position);
+                position += 1;
+                quote! { ::axum_wayfind::__private::write_wildcard(f, &#field)?; }
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl ::axum_wayfind::typed::WayfindTypedPath for #ident {
+            const PATH: &'static str = #template;
+        }
+
+        impl ::core::fmt::Display for #ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #(#writes)*
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        impl<S> ::axum_wayfind::__private::FromRequestParts<S> for #ident
+        where
+            S: ::core::marker::Send + ::core::marker::Sync,
+        {
+            type Rejection = ::axum_wayfind::extract::path::PathRejection;
+
+            async fn from_request_parts(
+                parts: &mut ::axum_wayfind::__private::Parts,
+                state: &S,
+            ) -> ::core::result::Result<Self, Self::Rejection> {
+                <::axum_wayfind::extract::Path<Self> as ::axum_wayfind::__private::FromRequestParts<S>>::from_request_parts(parts, state)
+                    .await
+                    .map(|path| path.0)
+            }
+        }
+    })
+}
+
+/// The template from `#[typed_path("...")]`.
+fn template_attr(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("typed_path"));
+
+    let Some(attr) = attrs.next() else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "missing `#[typed_path(\"/...\")]` attribute",
+        ));
+    };
+    if let Some(duplicate) = attrs.next() {
+        return Err(syn::Error::new_spanned(
+            duplicate,
+            "duplicate `#[typed_path]` attribute",
+        ));
+    }
+
+    attr.parse_args()
+}
+
+/// Check that the template's static `text` can be copied into a URI path
+/// as-is, as the generated `Display` impl does: only values are
+/// percent-encoded.
+fn check_uri_text(template: &LitStr, text: &str) -> syn::Result<()> {
+    let bytes = text.as_bytes();
+    let mut idx = 0;
+    while let Some(&byte) = bytes.get(idx) {
+        let valid = match byte {
+            b'%' => bytes
+                .get(idx + 1..idx + 3)
+                .is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)),
+            _ => byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/".contains(&byte),
+        };
+        if !valid {
+            let invalid = text[idx..].chars().next().unwrap_or_default();
+            return Err(syn::Error::new(
+                template.span(),
+                format!(
+                    "`{invalid}` isn't valid in a URI path; percent-encode it in the template: `{}`",
+                    template.value()
+                ),
+            ));
+        }
+        idx += 1;
+    }
+    Ok(())
+}
+
+/// How the generated `Display` impl reads each parameter's value.
+enum FieldAccess<'a> {
+    /// `self.field`, looked up by the parameter name serde reads it from.
+    Named(Vec<(String, &'a Ident)>),
+    /// `self.0`, `self.1`, … in template order.
+    Unnamed,
+    /// No fields, and no parameters.
+    Unit,
+}
+
+impl<'a> FieldAccess<'a> {
+    /// Check that the fields and the template's parameters correspond.
+    fn new(fields: &'a Fields, params: &[&str], template: &LitStr) -> syn::Result<Self> {
+        match fields {
+            Fields::Named(named) => {
+                let mut idents = Vec::with_capacity(named.named.len());
+                for field in &named.named {
+                    if let Some(ident) = &field.ident {
+                        idents.push((path_fields::param_name(field)?, ident));
+                    }
+                }
+
+                for (name, ident) in &idents {
+                    if !params.contains(&name.as_str()) {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!(
+                                "field `{name}` is not a parameter of `{}`",
+                                template.value()
+                            ),
+                        ));
+                    }
+                }
+                for param in params {
+                    if !idents.iter().any(|(name, _)| name == param) {
+                        return Err(syn::Error::new(
+                            template.span(),
+                            format!("parameter `{param}` has no matching field"),
+                        ));
+                    }
+                }
+
+                Ok(Self::Named(idents))
+            }
+            Fields::Unnamed(unnamed) => {
+                if unnamed.unnamed.len() != params.len() {
+                    return Err(syn::Error::new(
+                        unnamed.span(),
+                        format!(
+                            "`{}` has {} parameter(s) but the struct has {} field(s)",
+                            template.value(),
+                            params.len(),
+                            unnamed.unnamed.len()
+                        ),
+                    ));
+                }
+                Ok(Self::Unnamed)
+            }
+            Fields::Unit => {
+                if let Some(param) = params.first() {
+                    return Err(syn::Error::new(
+                        template.span(),
+                        format!("parameter `{param}` has no matching field"),
+                    ));
+                }
+                Ok(Self::Unit)
+            }
+        }
+    }
+
+    /// The expression reading parameter `name`, the `position`th in the
+    /// template.
+    fn access(&self, name: &str, position: usize) -> TokenStream {
+        match self {
+            Self::Named(idents) => {
+                let ident = idents
+                    .iter()
+                    .find(|(field_name, _)| field_name == name)
+                    .map(|(_, ident)| ident);
+                quote! { self.#ident }
+            }
+            Self::Unnamed => {
+                let index = Index::from(position);
+                quote! { self.#index }
+            }
+            // Unreachable: a unit struct has no parameters to access.
+            Self::Unit => TokenStream::new(),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// ==============================================================================
+// Compile-Time Template Checks
+// ==============================================================================
+//
+// `route!` validates a template with the same parser `Router::route` uses,
+// so syntax errors surface when the crate compiles rather than when the
+// router is built. Given a `PathFields` type, it also checks the type's
+// fields against the template's parameters in a const assertion.
+
+pub use axum_wayfind_macros::PathFields;
+
+/// The parameter names a [`Path`](crate::extract::Path) struct reads.
+///
+/// Derive it and pass the type to [`route!`](crate::route) to have each
+/// field checked against the template at compile time:
+///
+/// ```rust
+/// use axum_wayfind::{Router, checked::PathFields, extract::Path, route};
+/// use axum::routing::get;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, PathFields)]
+/// struct Params {
+///     user_id: u32,
+///     #[serde(rename = "team")]
+///     team_slug: String,
+/// }
+///
+/// async fn handler(Path(params): Path<Params>) {}
+///
+/// let app: Router = Router::new()
+///     .route(route!("/teams/{team}/users/{user_id}", Params), get(handler));
+/// ```
+///
+/// A misspelled field is a compile error:
+///
+/// ```rust,compile_fail
+/// # use axum_wayfind::{checked::PathFields, route};
+/// #[derive(serde::Deserialize, PathFields)]
+/// struct Params {
+///     user: u32,
+/// }
+///
+/// let path = route!("/users/{user_id}", Params);
+/// ```
+///
+/// The derive follows serde: `#[serde(rename = "...")]` is honored, and
+/// fields serde can leave out (`Option`, `#[serde(default)]`,
+/// `#[serde(skip)]`) aren't required. `#[serde(flatten)]` fields, and nested
+/// structs marked `#[path_fields(nested)]`, contribute the fields of their
+/// own `PathFields` impl. Template parameters without a field are fine:
+/// `Path` ignores them.
+pub trait PathFields {
+    /// The fields, in declaration order.
+    const FIELDS: &'static [PathField];
+}
+
+/// A field of a [`PathFields`] type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathField {
+    /// A field read from the parameter with this name.
+    Param(&'static str),
+    /// A nested or flattened struct, reading these fields.
+    Nested(&'static [Self]),
+}
+
+/// Whether every field in `fields` is one of `params`. Used by `route!`.
+#[doc(hidden)]
+#[must_use]
+pub const fn fields_are_params(fields: &[PathField], params: &[&str]) -> bool {
+    let mut idx = 0;
+    while idx < fields.len() {
+        let ok = match fields[idx] {
+            PathField::Param(name) => contains(params, name),
+            PathField::Nested(nested) => fields_are_params(nested, params),
+        };
+        if !ok {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+/// `params.contains(&name)`, in a const context.
+const fn contains(params: &[&str], name: &str) -> bool {
+    let mut idx = 0;
+    while idx < params.len() {
+        if str_eq(params[idx], name) {
+            return true;
+        }
+        idx += 1;
+    }
+    false
+}
+
+/// `a == b`, in a const context.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut idx = 0;
+    while idx < a.len() {
+        if a[idx] != b[idx] {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
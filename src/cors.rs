@@ -0,0 +1,211 @@
+// ==============================================================================
+// Router-aware CORS
+// ==============================================================================
+//
+// tower-http's `CorsLayer` sits in front of the router, so it has to be told
+// which methods to allow, and those lists drift from what's registered. Here
+// the router answers preflights itself once it has matched the route, with
+// the methods that route accepts. The origin policy comes from the route's
+// metadata (`Router::route_cors`) or the router's default (`Router::cors`).
+
+use std::time::Duration;
+
+use axum_core::extract::Request;
+use http::{
+    HeaderMap, HeaderName, HeaderValue, Method,
+    header::{
+        ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+        ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+        ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+    },
+};
+
+use crate::inspect::AllowedMethods;
+
+/// A CORS policy for [`Router::cors`](crate::Router::cors) and
+/// [`Router::route_cors`](crate::Router::route_cors).
+///
+/// The allowed methods aren't part of the policy: a preflight is answered
+/// with the methods the matched route accepts.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use axum_wayfind::cors::CorsPolicy;
+/// use http::{HeaderValue, header};
+///
+/// let policy = CorsPolicy::new()
+///     .allow_origin(HeaderValue::from_static("https://app.example.com"))
+///     .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
+///     .allow_credentials()
+///     .max_age(Duration::from_secs(600));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct CorsPolicy {
+    /// Allow every origin, ignoring `allowed_origins`.
+    any_origin: bool,
+    /// Compared byte for byte with the `Origin` header.
+    allowed_origins: Vec<HeaderValue>,
+    allow_headers: Vec<HeaderName>,
+    expose_headers: Vec<HeaderName>,
+    allow_credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl CorsPolicy {
+    /// A policy allowing no origins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow requests from `origin`, e.g. `https://app.example.com`.
+    pub fn allow_origin(mut self, origin: HeaderValue) -> Self {
+        self.allowed_origins.push(origin);
+        self
+    }
+
+    /// Allow requests from any origin.
+    ///
+    /// With [credentials](Self::allow_credentials), the request's origin is
+    /// echoed back instead of `*`, which browsers reject in that case.
+    pub const fn allow_any_origin(mut self) -> Self {
+        self.any_origin = true;
+        self
+    }
+
+    /// Request headers a cross-origin request may send, beyond the
+    /// CORS-safelisted ones.
+    pub fn allow_headers(mut self, headers: impl IntoIterator<Item = HeaderName>) -> Self {
+        self.allow_headers.extend(headers);
+        self
+    }
+
+    /// Response headers a cross-origin caller may read, beyond the
+    /// CORS-safelisted ones.
+    pub fn expose_headers(mut self, headers: impl IntoIterator<Item = HeaderName>) -> Self {
+        self.expose_headers.extend(headers);
+        self
+    }
+
+    /// Allow cookies and other credentials on cross-origin requests.
+    pub const fn allow_credentials(mut self) -> Self {
+        self.allow_credentials = true;
+        self
+    }
+
+    /// How long a browser may cache a preflight response.
+    pub const fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// The `Access-Control-Allow-Origin` value for `origin`, if allowed.
+    fn allow_origin_value(&self, origin: &HeaderValue) -> Option<HeaderValue> {
+        if !self.any_origin {
+            return self
+                .allowed_origins
+                .contains(origin)
+                .then(|| origin.clone());
+        }
+        if self.allow_credentials {
+            return Some(origin.clone());
+        }
+        Some(HeaderValue::from_static("*"))
+    }
+
+    /// Headers common to preflight and actual responses: the allowed origin,
+    /// credentials, and `Vary: Origin` when the answer depends on it.
+    fn origin_headers(&self, origin: Option<&HeaderValue>) -> Vec<(HeaderName, HeaderValue)> {
+        let mut headers = Vec::new();
+        if !self.any_origin || self.allow_credentials {
+            headers.push((VARY, HeaderValue::from_static("origin")));
+        }
+
+        if let Some(allowed) = origin.and_then(|origin| self.allow_origin_value(origin)) {
+            headers.push((ACCESS_CONTROL_ALLOW_ORIGIN, allowed));
+            if self.allow_credentials {
+                headers.push((
+                    ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                    HeaderValue::from_static("true"),
+                ));
+            }
+        }
+        headers
+    }
+
+    /// Headers for the 204 answering a preflight to a route accepting
+    /// `allowed`. A disallowed origin or method gets no `Access-Control-*`
+    /// headers, so the browser blocks the request.
+    pub(crate) fn preflight(
+        &self,
+        request: &HeaderMap,
+        allowed: &AllowedMethods,
+    ) -> Vec<(HeaderName, HeaderValue)> {
+        let origin = request.get(ORIGIN);
+        let requested = request
+            .get(ACCESS_CONTROL_REQUEST_METHOD)
+            .and_then(|method| Method::from_bytes(method.as_bytes()).ok());
+
+        let method_allowed = requested
+            .as_ref()
+            .is_some_and(|method| allowed.contains(method));
+        if !method_allowed
+            || origin
+                .and_then(|origin| self.allow_origin_value(origin))
+                .is_none()
+        {
+            return self.origin_headers(None);
+        }
+
+        let mut headers = self.origin_headers(origin);
+
+        let methods = match allowed {
+            AllowedMethods::Only(methods) => join(methods.iter().map(Method::as_str)),
+            // Every method is accepted; confirm the one asked about.
+            AllowedMethods::Any => join(requested.iter().map(Method::as_str)),
+        };
+        headers.extend(methods.map(|methods| (ACCESS_CONTROL_ALLOW_METHODS, methods)));
+
+        headers.extend(
+            join(self.allow_headers.iter().map(HeaderName::as_str))
+                .map(|names| (ACCESS_CONTROL_ALLOW_HEADERS, names)),
+        );
+        if let Some(max_age) = self.max_age {
+            headers.push((ACCESS_CONTROL_MAX_AGE, max_age.as_secs().into()));
+        }
+        headers
+    }
+
+    /// Headers to add to the response of an actual cross-origin request.
+    pub(crate) fn actual(&self, request: &HeaderMap) -> Vec<(HeaderName, HeaderValue)> {
+        let origin = request.get(ORIGIN);
+        let mut headers = self.origin_headers(origin);
+
+        if origin.is_some_and(|origin| self.allow_origin_value(origin).is_some()) {
+            headers.extend(
+                join(self.expose_headers.iter().map(HeaderName::as_str))
+                    .map(|names| (ACCESS_CONTROL_EXPOSE_HEADERS, names)),
+            );
+        }
+        headers
+    }
+}
+
+/// Whether `request` is a CORS preflight.
+pub(crate) fn is_preflight(request: &Request) -> bool {
+    request.method() == Method::OPTIONS
+        && request.headers().contains_key(ORIGIN)
+        && request
+            .headers()
+            .contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+}
+
+/// `items` as a comma-separated header value, or `None` if there are none.
+fn join<'a>(items: impl Iterator<Item = &'a str>) -> Option<HeaderValue> {
+    let joined = items.collect::<Vec<_>>().join(",");
+    if joined.is_empty() {
+        return None;
+    }
+    HeaderValue::try_from(joined).ok()
+}
@@ -1,12 +1,27 @@
 //! Extractors for `axum-wayfind`.
 //!
-//! Re-exports [`Path`] and [`MatchedPath`] which read from our own request
-//! extensions rather than axum's internal types.
+//! Re-exports [`Path`], [`PathParam`], [`RawPathParams`], [`MatchedPath`]
+//! and [`MatchedAlias`] which read from our own request extensions rather
+//! than axum's internal types.
 
-/// Matched-path extractor that records which route pattern was matched.
+/// Matched-path and matched-alias extractors that record which route
+/// pattern was matched.
 pub mod matched_path;
 /// Path parameter extractor with percent-decoding and serde deserialization.
 pub mod path;
+/// Single named path parameter extractor parsed with `FromStr`.
+pub mod path_param;
+/// Raw path parameter extractor that preserves the undecoded values.
+pub mod raw_path_params;
+/// Pluggable rendering of `Path` and `MatchedPath` rejections.
+pub mod rejection;
+/// Path parameter extractor that validates after deserializing.
+#[cfg(feature = "validation")]
+pub mod validated_path;
 
-pub use matched_path::MatchedPath;
+pub use matched_path::{MatchedAlias, MatchedPath};
 pub use path::Path;
+pub use path_param::PathParam;
+pub use raw_path_params::RawPathParams;
+#[cfg(feature = "validation")]
+pub use validated_path::ValidatedPath;
@@ -5,12 +5,18 @@
 // Our own `MatchedPath` since axum's constructor is `pub(crate)`. This
 // extractor reads from an extension we insert during dispatch containing
 // the original Axum-syntax template (e.g. "/users/{id}").
+//
+// `MatchedAlias` is the template that actually matched when a route has
+// aliases. Its extension is only inserted when an alias matched; otherwise
+// it is the same as `MatchedPath` and is read from that.
 
 use axum_core::extract::FromRequestParts;
 use axum_core::response::{IntoResponse, Response};
-use http::{StatusCode, request::Parts};
+use http::request::Parts;
 use std::{convert::Infallible, fmt, sync::Arc};
 
+use super::rejection::{self, InstalledFormatter, Rejection};
+
 /// Access the original route template that matched the current request.
 ///
 /// The returned string is the route pattern as registered (e.g.
@@ -51,22 +57,22 @@ where
             .extensions
             .get::<Self>()
             .cloned()
-            .ok_or(MatchedPathRejection)
+            .ok_or_else(|| MatchedPathRejection {
+                formatter: parts.extensions.get::<InstalledFormatter>().cloned(),
+            })
     }
 }
 
 /// Rejection for [`MatchedPath`] — returned when no matched path was
 /// found in the request extensions.
 #[derive(Debug)]
-pub struct MatchedPathRejection;
+pub struct MatchedPathRejection {
+    formatter: Option<InstalledFormatter>,
+}
 
 impl IntoResponse for MatchedPathRejection {
     fn into_response(self) -> Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Matched path is not available",
-        )
-            .into_response()
+        rejection::render(self.formatter.as_ref(), &Rejection::MissingMatchedPath)
     }
 }
 
@@ -92,3 +98,72 @@ where
         Ok(parts.extensions.get::<Self>().cloned())
     }
 }
+
+// ==============================================================================
+// MatchedAlias Extractor
+// ==============================================================================
+
+/// Access the template that matched the current request, which may be an
+/// alias registered with [`Router::route_aliases`](crate::Router::route_aliases).
+///
+/// [`MatchedPath`] always reports the route's canonical template; this
+/// reports the alias instead when one matched, and is otherwise the same.
+///
+/// ```rust,no_run
+/// use axum_wayfind::{Router, extract::{MatchedAlias, MatchedPath}};
+/// use axum::routing::get;
+///
+/// // For `/u/7`: path "/users/{id}", alias "/u/{id}".
+/// let app = Router::new().route_aliases(
+///     &["/users/{id}", "/u/{id}"],
+///     get(|path: MatchedPath, alias: MatchedAlias| async move {
+///         format!("{} via {}", path.as_str(), alias.as_str())
+///     }),
+/// );
+/// # let _: Router = app;
+/// ```
+#[derive(Clone, Debug)]
+pub struct MatchedAlias(pub(crate) Arc<str>);
+
+impl MatchedAlias {
+    /// Returns the template that matched as a `str` (e.g. `"/u/{id}"`).
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn from_extensions(extensions: &http::Extensions) -> Option<Self> {
+        extensions.get::<Self>().cloned().or_else(|| {
+            extensions
+                .get::<MatchedPath>()
+                .map(|path| Self(Arc::clone(&path.0)))
+        })
+    }
+}
+
+impl<S> FromRequestParts<S> for MatchedAlias
+where
+    S: Send + Sync,
+{
+    type Rejection = MatchedPathRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_extensions(&parts.extensions).ok_or_else(|| MatchedPathRejection {
+            formatter: parts.extensions.get::<InstalledFormatter>().cloned(),
+        })
+    }
+}
+
+impl<S> axum_core::extract::OptionalFromRequestParts<S> for MatchedAlias
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        Ok(Self::from_extensions(&parts.extensions))
+    }
+}
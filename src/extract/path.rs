@@ -8,25 +8,97 @@
 
 pub(crate) mod de;
 
+#[cfg(feature = "validation")]
+use super::validated_path::FailedToValidatePathParams;
+use super::{
+    raw_path_params::RawPathParam,
+    rejection::{self, InstalledFormatter, Rejection},
+};
+
 use axum_core::{
-    extract::FromRequestParts,
+    extract::{FromRequestParts, OptionalFromRequestParts},
     response::{IntoResponse, Response},
 };
-use http::{StatusCode, request::Parts};
+use http::{StatusCode, request::Parts, uri::PathAndQuery};
 use serde::de::DeserializeOwned;
 use std::{
     fmt,
-    ops::{Deref, DerefMut},
+    ops::{Deref, DerefMut, Range},
     sync::Arc,
 };
 
+// ==============================================================================
+// SharedStr — parameter text without a per-request copy
+// ==============================================================================
+
+/// Text that is either a slice of the request's path, sharing its buffer,
+/// or an owned string.
+///
+/// Captured values point into the request's `PathAndQuery` (a refcounted
+/// buffer), so capturing a parameter, or decoding one that contains no
+/// `%`, doesn't allocate.
+#[derive(Clone)]
+pub(crate) enum SharedStr {
+    /// `path.as_str()[range]`.
+    Path {
+        path: PathAndQuery,
+        range: Range<usize>,
+    },
+    /// Text with no request buffer to borrow from, e.g. decoded output.
+    Owned(Arc<str>),
+}
+
+impl SharedStr {
+    /// `value`, as a slice of `path` if it lies within it, else copied.
+    pub(crate) fn new(value: &str, path: Option<&PathAndQuery>) -> Self {
+        let range = path.and_then(|path| {
+            let start = value
+                .as_ptr()
+                .addr()
+                .checked_sub(path.as_str().as_ptr().addr())?;
+            let range = start..start + value.len();
+            (path.as_str().get(range.clone()) == Some(value)).then_some(range)
+        });
+
+        match (path, range) {
+            (Some(path), Some(range)) => Self::Path {
+                path: path.clone(),
+                range,
+            },
+            _ => Self::Owned(value.into()),
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            Self::Path { path, range } => path.as_str().get(range.clone()).unwrap_or_default(),
+            Self::Owned(value) => value,
+        }
+    }
+}
+
+impl Deref for SharedStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for SharedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
 // ==============================================================================
 // PercentDecodedStr
 // ==============================================================================
 
 /// A string that has been percent-decoded from a URL path parameter.
 #[derive(Clone, Debug)]
-pub struct PercentDecodedStr(Arc<str>);
+pub struct PercentDecodedStr(SharedStr);
 
 impl PercentDecodedStr {
     /// Attempt to percent-decode the given string.
@@ -38,13 +110,32 @@ impl PercentDecodedStr {
     pub fn new<S: AsRef<str>>(s: S) -> Result<Self, std::str::Utf8Error> {
         percent_encoding::percent_decode(s.as_ref().as_bytes())
             .decode_utf8()
-            .map(|decoded| Self(decoded.as_ref().into()))
+            .map(|decoded| Self(SharedStr::Owned(decoded.as_ref().into())))
+    }
+
+    /// Percent-decode a captured value, or `None` if the result isn't valid
+    /// UTF-8 and `lossy_utf8` is off.
+    ///
+    /// A value without `%` is its own decoding and shares `raw`'s buffer.
+    /// With `lossy_utf8`, invalid sequences are replaced with U+FFFD.
+    pub(crate) fn decode(raw: &SharedStr, lossy_utf8: bool) -> Option<Self> {
+        if !raw.contains('%') {
+            return Some(Self(raw.clone()));
+        }
+
+        let decoded = percent_encoding::percent_decode_str(raw);
+        let decoded = if lossy_utf8 {
+            decoded.decode_utf8_lossy()
+        } else {
+            decoded.decode_utf8().ok()?
+        };
+        Some(Self(SharedStr::Owned(decoded.as_ref().into())))
     }
 
     /// Returns the decoded string as a `&str`.
     #[must_use]
     pub fn as_str(&self) -> &str {
-        &self.0
+        self.0.as_str()
     }
 }
 
@@ -64,7 +155,7 @@ impl std::str::FromStr for PercentDecodedStr {
     type Err = std::convert::Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(s.into()))
+        Ok(Self(SharedStr::Owned(s.into())))
     }
 }
 
@@ -72,46 +163,90 @@ impl std::str::FromStr for PercentDecodedStr {
 // WayfindUrlParams — the extension type we insert during dispatch
 // ==============================================================================
 
+/// A parameter name from a route template, interned at registration.
+#[derive(Clone, Debug)]
+pub(crate) struct ParamKey {
+    name: Arc<str>,
+    /// Whether the parameter is a `{*wildcard}`, whose value can span
+    /// several segments.
+    wildcard: bool,
+}
+
+impl ParamKey {
+    pub(crate) fn new(name: &str, wildcard: bool) -> Self {
+        Self {
+            name: Arc::from(name),
+            wildcard,
+        }
+    }
+}
+
 /// Extracted URL parameters from a wayfind match, stored as a request
 /// extension. This is our equivalent of axum's internal `UrlParams`.
+///
+/// Every capture is kept in raw form and decoded on demand, so handlers that
+/// never read their parameters don't pay for decoding, and a parameter that
+/// isn't valid UTF-8 only affects extractors that need it as a string.
 #[derive(Clone, Debug)]
-pub(crate) enum WayfindUrlParams {
-    /// Successfully decoded parameters.
-    Params(Vec<(Arc<str>, PercentDecodedStr)>),
-    /// A parameter contained bytes that are not valid UTF-8 after
-    /// percent-decoding.
-    InvalidUtf8InPathParam {
-        /// The parameter name that contained invalid UTF-8.
-        key: Arc<str>,
-    },
+pub(crate) struct WayfindUrlParams {
+    /// Every user-visible parameter, in template order.
+    params: Arc<[RawPathParam]>,
 }
 
 impl WayfindUrlParams {
-    /// Build `WayfindUrlParams` from a wayfind `Match`, percent-decoding each
-    /// parameter value.
+    /// Build `WayfindUrlParams` from a wayfind `Match`.
+    ///
+    /// `path` is the request path the match was made against; values are
+    /// stored as slices of it rather than copied. `keys` are the route's
+    /// parameter names, interned at registration, so keys aren't copied
+    /// either. Nothing is decoded yet: each value is percent-decoded the
+    /// first time an extractor reads it. With `lossy_utf8`, values that
+    /// aren't valid UTF-8 once decoded get U+FFFD replacement characters
+    /// instead of no decoded form at all.
     #[must_use]
-    pub(crate) fn from_match<T>(matched: &wayfind::Match<'_, '_, T>) -> Self {
-        let mut params = Vec::with_capacity(matched.parameters.len());
-
-        for (key, value) in &matched.parameters {
+    pub(crate) fn from_match<T>(
+        matched: &wayfind::Match<'_, '_, T>,
+        path: Option<&PathAndQuery>,
+        keys: &[ParamKey],
+        lossy_utf8: bool,
+    ) -> Self {
+        let params = matched
+            .parameters
+            .iter()
             // Skip internal parameters used by the nesting infrastructure
             // (e.g. the wildcard tail param in `nest_service` routes).
-            if key.starts_with("__private_") {
-                continue;
-            }
-
-            let key: Arc<str> = Arc::from(*key);
-
-            match PercentDecodedStr::new(*value) {
-                Ok(decoded) => params.push((key, decoded)),
-                Err(_) => return Self::InvalidUtf8InPathParam { key },
-            }
-        }
+            .filter(|(key, _)| !key.starts_with("__private_"))
+            .map(|(key, value)| {
+                let (key, wildcard) = keys
+                    .iter()
+                    .find(|interned| interned.name.as_ref() == *key)
+                    .map_or_else(
+                        || (Arc::from(*key), false),
+                        |interned| (Arc::clone(&interned.name), interned.wildcard),
+                    );
+                RawPathParam::new(key, SharedStr::new(value, path), lossy_utf8, wildcard)
+            })
+            .collect();
+
+        Self { params }
+    }
 
-        Self::Params(params)
+    /// Every parameter in raw and decoded form, in template order.
+    pub(crate) const fn params(&self) -> &Arc<[RawPathParam]> {
+        &self.params
     }
 }
 
+/// Deserialize path parameters into `T`, exactly as [`Path`] does.
+pub(crate) fn deserialize_params<T>(
+    params: &[RawPathParam],
+) -> Result<T, FailedToDeserializePathParams>
+where
+    T: DeserializeOwned,
+{
+    de::deserialize(params).map_err(FailedToDeserializePathParams::new)
+}
+
 // ==============================================================================
 // Path<T>
 // ==============================================================================
@@ -132,6 +267,38 @@ impl WayfindUrlParams {
 /// let app = Router::new().route("/users/{id}", get(handler));
 /// # let _: Router = app;
 /// ```
+///
+/// Byte targets such as `bytes::Bytes` (with its `serde` feature) receive
+/// the exact percent-decoded bytes, so parameters that aren't valid UTF-8
+/// can still be extracted. A bare `Vec<u8>` is deserialized as a sequence,
+/// not as bytes.
+///
+/// Sequence targets receive a parameter's `/`-separated segments, each
+/// percent-decoded on its own, so `%2F` stays inside its segment. This is
+/// how a wildcard capture is extracted piece by piece, either as the whole
+/// `Path<Vec<T>>` or as a `Vec<T>` field of a struct:
+///
+/// ```rust,no_run
+/// use axum_wayfind::{Router, extract::Path};
+/// use axum::routing::get;
+///
+/// // `/files/docs/a%2Fb.txt` yields `["docs", "a/b.txt"]`.
+/// async fn handler(Path(segments): Path<Vec<String>>) {
+///     println!("{segments:?}");
+/// }
+///
+/// let app = Router::new().route("/files/{*path}", get(handler));
+/// # let _: Router = app;
+/// ```
+///
+/// With more than one parameter, `Path<Vec<T>>` is a sequence of the
+/// parameter values instead, as in axum.
+///
+/// Struct fields without a parameter of their own are deserialized as nested
+/// structs (or newtypes around them) from all parameters, so shared groups
+/// of parameters can be factored out. `#[serde(flatten)]` works too, but
+/// serde buffers flattened values as strings: their fields must deserialize
+/// from a string, e.g. with `serde_with::DisplayFromStr` for numbers.
 #[derive(Debug)]
 pub struct Path<T>(pub T);
 
@@ -157,29 +324,54 @@ where
     type Rejection = PathRejection;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        // Extract into a helper fn so it's compiled once regardless of T.
-        fn get_params(parts: &Parts) -> Result<&[(Arc<str>, PercentDecodedStr)], PathRejection> {
-            match parts.extensions.get::<WayfindUrlParams>() {
-                Some(WayfindUrlParams::Params(params)) => Ok(params),
-                Some(WayfindUrlParams::InvalidUtf8InPathParam { key }) => {
-                    let err = PathDeserializationError {
-                        kind: ErrorKind::InvalidUtf8InPathParam {
-                            key: key.to_string(),
-                        },
-                    };
-                    Err(PathRejection::FailedToDeserializePathParams(
-                        FailedToDeserializePathParams(err),
-                    ))
-                }
-                None => Err(PathRejection::MissingPathParams),
-            }
-        }
+        let formatter = || parts.extensions.get::<InstalledFormatter>().cloned();
+
+        let params = parts.extensions.get::<WayfindUrlParams>().ok_or_else(|| {
+            PathRejection::MissingPathParams(MissingPathParams {
+                formatter: formatter(),
+            })
+        })?;
+
+        deserialize_params(params.params())
+            .map(Self)
+            .map_err(|err| {
+                PathRejection::FailedToDeserializePathParams(err.with_formatter(formatter()))
+            })
+    }
+}
+
+/// `Option<Path<T>>` is `None` when the request carries no path parameters
+/// but `T` needs some: it was handled by a fallback, or by a nested router's
+/// fallback whose internal tail capture is filtered out. A `T` that accepts
+/// no parameters, such as a map, is `Some` and empty. If parameters are
+/// present but fail to deserialize, the request is still rejected. All of
+/// this matches axum 0.8.
+impl<T, S> OptionalFromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = PathRejection;
 
-        match T::deserialize(de::PathDeserializer::new(get_params(parts)?)) {
-            Ok(val) => Ok(Self(val)),
-            Err(e) => Err(PathRejection::FailedToDeserializePathParams(
-                FailedToDeserializePathParams(e),
-            )),
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        let Some(params) = parts.extensions.get::<WayfindUrlParams>() else {
+            return Ok(None);
+        };
+
+        match deserialize_params(params.params()) {
+            Ok(val) => Ok(Some(Self(val))),
+            Err(err) if matches!(err.kind(), ErrorKind::WrongNumberOfParameters { got: 0, .. }) => {
+                Ok(None)
+            }
+            Err(err) => {
+                let formatter = parts.extensions.get::<InstalledFormatter>().cloned();
+                Err(PathRejection::FailedToDeserializePathParams(
+                    err.with_formatter(formatter),
+                ))
+            }
         }
     }
 }
@@ -300,6 +492,12 @@ pub enum ErrorKind {
         key: String,
     },
 
+    /// The matched route has no parameter with the requested name.
+    MissingParam {
+        /// The parameter name that was requested.
+        key: String,
+    },
+
     /// Tried to deserialize into an unsupported type such as nested maps.
     UnsupportedType {
         /// The name of the unsupported type.
@@ -341,6 +539,7 @@ impl fmt::Display for ErrorKind {
                 Ok(())
             }
             Self::UnsupportedType { name } => write!(f, "Unsupported type `{name}`"),
+            Self::MissingParam { key } => write!(f, "No path parameter named `{key}`"),
             Self::ParseErrorAtKey {
                 key,
                 value,
@@ -383,86 +582,107 @@ impl fmt::Display for ErrorKind {
 /// Rejection type for [`Path`] if the captured route params couldn't be
 /// deserialized into the expected type.
 #[derive(Debug)]
-pub struct FailedToDeserializePathParams(PathDeserializationError);
+pub struct FailedToDeserializePathParams {
+    error: PathDeserializationError,
+    formatter: Option<InstalledFormatter>,
+}
 
 impl FailedToDeserializePathParams {
+    pub(crate) const fn new(error: PathDeserializationError) -> Self {
+        Self {
+            error,
+            formatter: None,
+        }
+    }
+
+    pub(crate) fn with_formatter(mut self, formatter: Option<InstalledFormatter>) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
     /// Get a reference to the underlying error kind.
     #[must_use]
     pub const fn kind(&self) -> &ErrorKind {
-        &self.0.kind
+        &self.error.kind
     }
 
     /// Convert this error into the underlying error kind.
     #[must_use]
     pub fn into_kind(self) -> ErrorKind {
-        self.0.kind
+        self.error.kind
     }
 
     /// Get the response body text used for this rejection.
     #[must_use]
     pub fn body_text(&self) -> String {
-        match self.0.kind {
+        match self.error.kind {
             ErrorKind::Message(_)
             | ErrorKind::DeserializeError { .. }
             | ErrorKind::InvalidUtf8InPathParam { .. }
             | ErrorKind::ParseError { .. }
             | ErrorKind::ParseErrorAtIndex { .. }
-            | ErrorKind::ParseErrorAtKey { .. } => format!("Invalid URL: {}", self.0.kind),
-            ErrorKind::WrongNumberOfParameters { .. } | ErrorKind::UnsupportedType { .. } => {
-                self.0.kind.to_string()
-            }
+            | ErrorKind::ParseErrorAtKey { .. } => format!("Invalid URL: {}", self.error.kind),
+            ErrorKind::WrongNumberOfParameters { .. }
+            | ErrorKind::MissingParam { .. }
+            | ErrorKind::UnsupportedType { .. } => self.error.kind.to_string(),
         }
     }
 
     /// Get the status code used for this rejection.
     #[must_use]
     pub const fn status(&self) -> StatusCode {
-        match self.0.kind {
+        match self.error.kind {
             ErrorKind::Message(_)
             | ErrorKind::DeserializeError { .. }
             | ErrorKind::InvalidUtf8InPathParam { .. }
             | ErrorKind::ParseError { .. }
             | ErrorKind::ParseErrorAtIndex { .. }
             | ErrorKind::ParseErrorAtKey { .. } => StatusCode::BAD_REQUEST,
-            ErrorKind::WrongNumberOfParameters { .. } | ErrorKind::UnsupportedType { .. } => {
-                StatusCode::INTERNAL_SERVER_ERROR
-            }
+            ErrorKind::WrongNumberOfParameters { .. }
+            | ErrorKind::MissingParam { .. }
+            | ErrorKind::UnsupportedType { .. } => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
 
 impl IntoResponse for FailedToDeserializePathParams {
     fn into_response(self) -> Response {
-        (self.status(), self.body_text()).into_response()
+        rejection::render(
+            self.formatter.as_ref(),
+            &Rejection::FailedToDeserializePathParams(&self),
+        )
     }
 }
 
 impl fmt::Display for FailedToDeserializePathParams {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.0.fmt(f)
+        self.error.fmt(f)
     }
 }
 
 impl std::error::Error for FailedToDeserializePathParams {}
 
-/// Rejection for the [`Path`] extractor.
+/// Rejection for the [`Path`] extractor, and for
+/// [`ValidatedPath`](super::ValidatedPath) with the `validation` feature.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum PathRejection {
     /// Failed to deserialize the path parameters.
     FailedToDeserializePathParams(FailedToDeserializePathParams),
     /// No path parameters were found in the request extensions.
-    MissingPathParams,
+    MissingPathParams(MissingPathParams),
+    /// The parameters deserialized but failed validation.
+    #[cfg(feature = "validation")]
+    FailedToValidatePathParams(FailedToValidatePathParams),
 }
 
 impl IntoResponse for PathRejection {
     fn into_response(self) -> Response {
         match self {
             Self::FailedToDeserializePathParams(inner) => inner.into_response(),
-            Self::MissingPathParams => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "No path parameters found",
-            )
-                .into_response(),
+            Self::MissingPathParams(inner) => inner.into_response(),
+            #[cfg(feature = "validation")]
+            Self::FailedToValidatePathParams(inner) => inner.into_response(),
         }
     }
 }
@@ -471,9 +691,32 @@ impl fmt::Display for PathRejection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::FailedToDeserializePathParams(inner) => inner.fmt(f),
-            Self::MissingPathParams => write!(f, "No path parameters found"),
+            Self::MissingPathParams(inner) => inner.fmt(f),
+            #[cfg(feature = "validation")]
+            Self::FailedToValidatePathParams(inner) => inner.fmt(f),
         }
     }
 }
 
 impl std::error::Error for PathRejection {}
+
+/// Rejection type for [`Path`] if the request has no path parameters, e.g.
+/// because it was handled by a fallback.
+#[derive(Debug)]
+pub struct MissingPathParams {
+    pub(crate) formatter: Option<InstalledFormatter>,
+}
+
+impl IntoResponse for MissingPathParams {
+    fn into_response(self) -> Response {
+        rejection::render(self.formatter.as_ref(), &Rejection::MissingPathParams)
+    }
+}
+
+impl fmt::Display for MissingPathParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No path parameters found")
+    }
+}
+
+impl std::error::Error for MissingPathParams {}
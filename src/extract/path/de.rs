@@ -2,17 +2,77 @@
 // Serde PathDeserializer — ported from axum
 // ==============================================================================
 //
-// This is a direct port of axum's `extract/path/de.rs`, adapted to use our
-// own `PercentDecodedStr` type. The deserializer supports the same target
-// types as axum: single values, tuples, structs, `HashMap<String, String>`.
-
-use super::{ErrorKind, PathDeserializationError, PercentDecodedStr};
+// This is a direct port of axum's `extract/path/de.rs`, adapted to read from
+// our `RawPathParam`s. The deserializer supports the same target types as
+// axum: single values, tuples, structs, `HashMap<String, String>`. On top of
+// that, byte targets (`bytes::Bytes`, `serde_bytes::ByteBuf`) receive the
+// decoded bytes, so they work even when a parameter isn't valid UTF-8, and
+// sequence targets receive a parameter's `/`-separated segments.
+//
+// A parameter's decoded string is only required when the target type asks
+// for one, so invalid UTF-8 in one parameter doesn't fail the others.
+//
+// Struct fields without a parameter of their own are deserialized as nested
+// structs from all parameters. Which absent fields are structs is only known
+// once serde asks for a field's value, so `deserialize` retries without the
+// ones that turned out not to be, leaving them absent for serde's `default`.
+// `#[serde(flatten)]` goes through serde's
+// buffering instead, which only sees strings: flattened fields must
+// deserialize from a string (e.g. `String`, unit enums, or numbers via
+// `serde_with::DisplayFromStr`).
+
+use super::{ErrorKind, PathDeserializationError, PercentDecodedStr, RawPathParam};
 use serde::{
     Deserializer,
-    de::{DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor},
+    de::{
+        DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+        value::StringDeserializer,
+    },
     forward_to_deserialize_any,
 };
-use std::{any::type_name, sync::Arc};
+use std::{any::type_name, borrow::Cow, cell::Cell};
+
+/// Deserialize `T` from `url_params`, retrying without offering a field as a
+/// nested struct each time one turns out to be something else.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn deserialize<T>(url_params: &[RawPathParam]) -> Result<T, PathDeserializationError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let mut not_nested = Vec::new();
+    loop {
+        let nesting = Nesting {
+            not_nested: &not_nested,
+            found: Cell::new(None),
+        };
+        let result = T::deserialize(PathDeserializer::new(url_params, &nesting));
+        match nesting.found.get() {
+            Some(field) if result.is_err() && !not_nested.contains(&field) => {
+                not_nested.push(field);
+            }
+            _ => return result,
+        }
+    }
+}
+
+/// What one attempt of [`deserialize`] knows about fields without a
+/// parameter of their own.
+struct Nesting<'a> {
+    /// Fields known not to be nested structs, left absent.
+    not_nested: &'a [&'static str],
+    /// A field offered as a nested struct that turned out not to be one.
+    found: Cell<Option<&'static str>>,
+}
+
+/// The decoded string form of a parameter, or the `InvalidUtf8InPathParam`
+/// error `Path` reports when it has none.
+fn decoded_str(param: &RawPathParam) -> Result<&PercentDecodedStr, PathDeserializationError> {
+    param.decoded_str().ok_or_else(|| {
+        PathDeserializationError::new(ErrorKind::InvalidUtf8InPathParam {
+            key: param.key.to_string(),
+        })
+    })
+}
 
 macro_rules! unsupported_type {
     ($trait_fn:ident) => {
@@ -39,11 +99,12 @@ macro_rules! parse_single_value {
                     .expected(1));
             }
 
-            let value = match self.url_params[0].1.parse() {
+            let decoded = decoded_str(&self.url_params[0])?;
+            let value = match decoded.parse() {
                 Ok(v) => v,
                 Err(err) => {
                     return Err(PathDeserializationError::new(ErrorKind::ParseError {
-                        value: self.url_params[0].1.as_str().to_owned(),
+                        value: decoded.as_str().to_owned(),
                         expected_type: $ty,
                         message: format!("{err}"),
                     }));
@@ -55,20 +116,23 @@ macro_rules! parse_single_value {
 }
 #[allow(clippy::redundant_pub_crate)]
 pub(crate) struct PathDeserializer<'de> {
-    url_params: &'de [(Arc<str>, PercentDecodedStr)],
+    url_params: &'de [RawPathParam],
+    nesting: &'de Nesting<'de>,
 }
 
 impl<'de> PathDeserializer<'de> {
     #[inline]
-    pub(crate) const fn new(url_params: &'de [(Arc<str>, PercentDecodedStr)]) -> Self {
-        PathDeserializer { url_params }
+    const fn new(url_params: &'de [RawPathParam], nesting: &'de Nesting<'de>) -> Self {
+        PathDeserializer {
+            url_params,
+            nesting,
+        }
     }
 }
 
 impl<'de> Deserializer<'de> for PathDeserializer<'de> {
     type Error = PathDeserializationError;
 
-    unsupported_type!(deserialize_bytes);
     unsupported_type!(deserialize_option);
     unsupported_type!(deserialize_identifier);
     unsupported_type!(deserialize_ignored_any);
@@ -87,7 +151,6 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
     parse_single_value!(deserialize_f32, visit_f32, "f32");
     parse_single_value!(deserialize_f64, visit_f64, "f64");
     parse_single_value!(deserialize_string, visit_string, "String");
-    parse_single_value!(deserialize_byte_buf, visit_string, "String");
     parse_single_value!(deserialize_char, visit_char, "char");
 
     fn deserialize_any<V>(self, v: V) -> Result<V::Value, Self::Error>
@@ -97,6 +160,30 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
         self.deserialize_str(v)
     }
 
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.url_params.len() != 1 {
+            return Err(PathDeserializationError::wrong_number_of_parameters()
+                .got(self.url_params.len())
+                .expected(1));
+        }
+        visitor.visit_bytes(&self.url_params[0].decoded_bytes())
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.url_params.len() != 1 {
+            return Err(PathDeserializationError::wrong_number_of_parameters()
+                .got(self.url_params.len())
+                .expected(1));
+        }
+        visitor.visit_byte_buf(self.url_params[0].decoded_bytes().into_owned())
+    }
+
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
@@ -106,8 +193,8 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
                 .got(self.url_params.len())
                 .expected(1));
         }
-        let key = &self.url_params[0].0;
-        let value = &self.url_params[0].1;
+        let key = &self.url_params[0].key;
+        let value = decoded_str(&self.url_params[0])?;
         visitor
             .visit_str(value)
             .map_err(|e: PathDeserializationError| {
@@ -156,6 +243,15 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        // A lone wildcard is deserialized as its `/`-separated segments, so
+        // it can be extracted as `Path<Vec<String>>` — unless the elements
+        // are key/value pairs, as for any other parameter.
+        if let [param] = self.url_params
+            && param.wildcard
+        {
+            return visitor.visit_seq(WildcardSeqDeserializer::new(param));
+        }
+
         visitor.visit_seq(SeqDeserializer {
             params: self.url_params,
             idx: 0,
@@ -201,23 +297,34 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_map(MapDeserializer {
-            params: self.url_params,
-            value: None,
-            key: None,
-        })
+        visitor.visit_map(MapDeserializer::new(self.url_params, &[], self.nesting))
     }
 
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.deserialize_map(visitor)
+        // A field without a parameter of its own can only be a nested
+        // struct, which needs parameters no other field claims. Without
+        // any, absent fields are left to serde as before (so
+        // `#[serde(default)]` and `Option` fields keep working), as are
+        // fields an earlier attempt found aren't structs.
+        let has_unclaimed = self
+            .url_params
+            .iter()
+            .any(|param| !fields.iter().any(|field| *field == &*param.key));
+        let nested_fields = if has_unclaimed { fields } else { &[] };
+
+        visitor.visit_map(MapDeserializer::new(
+            self.url_params,
+            nested_fields,
+            self.nesting,
+        ))
     }
 
     fn deserialize_enum<V>(
@@ -236,7 +343,7 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
         }
 
         visitor.visit_enum(EnumDeserializer {
-            value: &self.url_params[0].1,
+            value: decoded_str(&self.url_params[0])?,
         })
     }
 }
@@ -246,9 +353,34 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
 // ==============================================================================
 
 struct MapDeserializer<'de> {
-    params: &'de [(Arc<str>, PercentDecodedStr)],
+    params: &'de [RawPathParam],
     key: Option<KeyOrIdx<'de>>,
-    value: Option<&'de PercentDecodedStr>,
+    value: Option<&'de RawPathParam>,
+    /// Every parameter, for nested structs to deserialize from.
+    all_params: &'de [RawPathParam],
+    /// Struct fields still to check for a parameter of their own. Those
+    /// without one are offered after the parameters, as nested structs.
+    nested_fields: &'static [&'static str],
+    nested: Option<&'static str>,
+    nesting: &'de Nesting<'de>,
+}
+
+impl<'de> MapDeserializer<'de> {
+    const fn new(
+        params: &'de [RawPathParam],
+        nested_fields: &'static [&'static str],
+        nesting: &'de Nesting<'de>,
+    ) -> Self {
+        Self {
+            params,
+            key: None,
+            value: None,
+            all_params: params,
+            nested_fields,
+            nested: None,
+            nesting,
+        }
+    }
 }
 
 impl<'de> MapAccess<'de> for MapDeserializer<'de> {
@@ -258,14 +390,24 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
     where
         K: DeserializeSeed<'de>,
     {
-        match self.params.split_first() {
-            Some(((key, value), tail)) => {
-                self.value = Some(value);
-                self.params = tail;
-                self.key = Some(KeyOrIdx::Key(key));
-                seed.deserialize(KeyDeserializer { key }).map(Some)
+        if let Some((param, tail)) = self.params.split_first() {
+            self.value = Some(param);
+            self.params = tail;
+            self.key = Some(KeyOrIdx::Key(&param.key));
+            seed.deserialize(KeyDeserializer { key: &param.key })
+                .map(Some)
+        } else {
+            while let Some((field, rest)) = self.nested_fields.split_first() {
+                self.nested_fields = rest;
+                if self.all_params.iter().any(|param| &*param.key == *field)
+                    || self.nesting.not_nested.contains(field)
+                {
+                    continue;
+                }
+                self.nested = Some(*field);
+                return seed.deserialize(KeyDeserializer { key: field }).map(Some);
             }
-            None => Ok(None),
+            Ok(None)
         }
     }
 
@@ -273,6 +415,14 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
     where
         V: DeserializeSeed<'de>,
     {
+        if let Some(field) = self.nested.take() {
+            return seed.deserialize(NestedDeserializer {
+                field,
+                params: self.all_params,
+                nesting: self.nesting,
+            });
+        }
+
         match self.value.take() {
             Some(value) => seed.deserialize(ValueDeserializer {
                 key: self.key.take(),
@@ -283,6 +433,83 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
     }
 }
 
+// ==============================================================================
+// NestedDeserializer
+// ==============================================================================
+//
+// The value of a struct field that has no parameter of its own. Structs and
+// maps (including newtypes around them) deserialize from every parameter,
+// picking the ones they name; anything else fails, noting the field so the
+// next attempt leaves it absent.
+
+struct NestedDeserializer<'de> {
+    field: &'static str,
+    params: &'de [RawPathParam],
+    nesting: &'de Nesting<'de>,
+}
+
+impl<'de> Deserializer<'de> for NestedDeserializer<'de> {
+    type Error = PathDeserializationError;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.nesting.found.set(Some(self.field));
+        Err(PathDeserializationError::missing_field(self.field))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        PathDeserializer::new(self.params, self.nesting).deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        PathDeserializer::new(self.params, self.nesting).deserialize_map(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // An absent `Option` field stays `None`, as it would without nesting.
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct enum identifier
+    }
+}
+
 // ==============================================================================
 // KeyDeserializer
 // ==============================================================================
@@ -333,7 +560,8 @@ macro_rules! parse_value {
         where
             V: Visitor<'de>,
         {
-            let v = match self.value.parse() {
+            let value = decoded_str(self.value)?;
+            let v = match value.parse() {
                 Ok(v) => v,
                 Err(err) => {
                     let message = format!("{err}");
@@ -341,13 +569,13 @@ macro_rules! parse_value {
                         let kind = match key {
                             KeyOrIdx::Key(key) => ErrorKind::ParseErrorAtKey {
                                 key: key.to_owned(),
-                                value: self.value.as_str().to_owned(),
+                                value: value.as_str().to_owned(),
                                 expected_type: $ty,
                                 message,
                             },
                             KeyOrIdx::Idx { idx: index, .. } => ErrorKind::ParseErrorAtIndex {
                                 index,
-                                value: self.value.as_str().to_owned(),
+                                value: value.as_str().to_owned(),
                                 expected_type: $ty,
                                 message,
                             },
@@ -355,7 +583,7 @@ macro_rules! parse_value {
                         PathDeserializationError::new(kind)
                     } else {
                         PathDeserializationError::new(ErrorKind::ParseError {
-                            value: self.value.as_str().to_owned(),
+                            value: value.as_str().to_owned(),
                             expected_type: $ty,
                             message,
                         })
@@ -370,7 +598,7 @@ macro_rules! parse_value {
 #[derive(Debug)]
 struct ValueDeserializer<'de> {
     key: Option<KeyOrIdx<'de>>,
-    value: &'de PercentDecodedStr,
+    value: &'de RawPathParam,
 }
 
 impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
@@ -393,7 +621,6 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
     parse_value!(deserialize_f32, visit_f32, "f32");
     parse_value!(deserialize_f64, visit_f64, "f64");
     parse_value!(deserialize_string, visit_string, "String");
-    parse_value!(deserialize_byte_buf, visit_string, "String");
     parse_value!(deserialize_char, visit_char, "char");
 
     fn deserialize_any<V>(self, v: V) -> Result<V::Value, Self::Error>
@@ -407,13 +634,14 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        let value = decoded_str(self.value)?;
         visitor
-            .visit_str(self.value)
+            .visit_str(value)
             .map_err(|e: PathDeserializationError| {
                 if let (ErrorKind::Message(message), Some(key)) = (&e.kind, self.key.as_ref()) {
                     PathDeserializationError::new(ErrorKind::DeserializeError {
                         key: key.key().to_owned(),
-                        value: self.value.as_str().to_owned(),
+                        value: value.as_str().to_owned(),
                         message: message.to_owned(),
                     })
                 } else {
@@ -426,7 +654,14 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_bytes(self.value.as_bytes())
+        visitor.visit_bytes(&self.value.decoded_bytes())
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_byte_buf(self.value.decoded_bytes().into_owned())
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -471,7 +706,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
     {
         struct PairDeserializer<'de> {
             key: Option<KeyOrIdx<'de>>,
-            value: Option<&'de PercentDecodedStr>,
+            value: Option<&'de RawPathParam>,
         }
 
         impl<'de> SeqAccess<'de> for PairDeserializer<'de> {
@@ -517,13 +752,11 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         }
     }
 
-    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        Err(PathDeserializationError::unsupported_type(type_name::<
-            V::Value,
-        >()))
+        visitor.visit_seq(SegmentsDeserializer::new(self.value))
     }
 
     fn deserialize_tuple_struct<V>(
@@ -563,7 +796,9 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_enum(EnumDeserializer { value: self.value })
+        visitor.visit_enum(EnumDeserializer {
+            value: decoded_str(self.value)?,
+        })
     }
 
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -643,7 +878,7 @@ impl<'de> VariantAccess<'de> for UnitVariant {
 // ==============================================================================
 
 struct SeqDeserializer<'de> {
-    params: &'de [(Arc<str>, PercentDecodedStr)],
+    params: &'de [RawPathParam],
     idx: usize,
 }
 
@@ -655,13 +890,16 @@ impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
         T: DeserializeSeed<'de>,
     {
         match self.params.split_first() {
-            Some(((key, value), tail)) => {
+            Some((param, tail)) => {
                 self.params = tail;
                 let idx = self.idx;
                 self.idx += 1;
                 Ok(Some(seed.deserialize(ValueDeserializer {
-                    key: Some(KeyOrIdx::Idx { idx, key }),
-                    value,
+                    key: Some(KeyOrIdx::Idx {
+                        idx,
+                        key: &param.key,
+                    }),
+                    value: param,
                 })?))
             }
             None => Ok(None),
@@ -669,6 +907,455 @@ impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
     }
 }
 
+// ==============================================================================
+// SegmentsDeserializer
+// ==============================================================================
+//
+// Splits a parameter's raw value on `/` before percent-decoding, so an
+// encoded `%2F` stays inside its segment instead of acting as a separator.
+// Empty segments (from `a//b` or a trailing slash) are kept.
+
+struct SegmentsDeserializer<'de> {
+    key: &'de str,
+    segments: std::str::Split<'de, char>,
+    lossy_utf8: bool,
+}
+
+impl<'de> SegmentsDeserializer<'de> {
+    fn new(param: &'de RawPathParam) -> Self {
+        Self {
+            key: &param.key,
+            segments: param.raw().split('/'),
+            lossy_utf8: param.lossy_utf8,
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for SegmentsDeserializer<'de> {
+    type Error = PathDeserializationError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.segments
+            .next()
+            .map(|raw| {
+                seed.deserialize(SegmentDeserializer {
+                    key: self.key,
+                    raw,
+                    lossy_utf8: self.lossy_utf8,
+                })
+            })
+            .transpose()
+    }
+}
+
+// ==============================================================================
+// WildcardSeqDeserializer
+// ==============================================================================
+//
+// A lone wildcard extracted as a sequence can mean its segments
+// (`Vec<String>`) or its key/value pair (`Vec<(String, String)>`), as any
+// other parameter would. Which one depends on the element type, so the first
+// element decides: a scalar switches to the segments, anything else gets the
+// whole parameter.
+
+struct WildcardSeqDeserializer<'de> {
+    param: &'de RawPathParam,
+    state: WildcardSeqState<'de>,
+}
+
+enum WildcardSeqState<'de> {
+    /// No element has been deserialized yet.
+    First,
+    /// The elements are scalars, read from the remaining segments.
+    Segments(SegmentsDeserializer<'de>),
+    /// The only element, the whole parameter, has been deserialized.
+    Done,
+}
+
+impl<'de> WildcardSeqDeserializer<'de> {
+    const fn new(param: &'de RawPathParam) -> Self {
+        Self {
+            param,
+            state: WildcardSeqState::First,
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for WildcardSeqDeserializer<'de> {
+    type Error = PathDeserializationError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match &mut self.state {
+            WildcardSeqState::First => seed
+                .deserialize(FirstElementDeserializer {
+                    param: self.param,
+                    state: &mut self.state,
+                })
+                .map(Some),
+            WildcardSeqState::Segments(segments) => segments.next_element_seed(seed),
+            WildcardSeqState::Done => Ok(None),
+        }
+    }
+}
+
+/// The first element of a [`WildcardSeqDeserializer`], which settles how the
+/// rest are read.
+struct FirstElementDeserializer<'a, 'de> {
+    param: &'de RawPathParam,
+    state: &'a mut WildcardSeqState<'de>,
+}
+
+impl<'de> FirstElementDeserializer<'_, 'de> {
+    /// Read the elements as segments, starting with the first.
+    fn segment(self) -> SegmentDeserializer<'de> {
+        let mut segments = SegmentsDeserializer::new(self.param);
+        let first = SegmentDeserializer {
+            key: segments.key,
+            raw: segments.segments.next().unwrap_or_default(),
+            lossy_utf8: segments.lossy_utf8,
+        };
+        *self.state = WildcardSeqState::Segments(segments);
+        first
+    }
+
+    /// Read the whole parameter as the only element.
+    fn value(self) -> ValueDeserializer<'de> {
+        *self.state = WildcardSeqState::Done;
+        ValueDeserializer {
+            key: Some(KeyOrIdx::Idx {
+                idx: 0,
+                key: &self.param.key,
+            }),
+            value: self.param,
+        }
+    }
+}
+
+macro_rules! forward_first_element {
+    ($to:ident: $($trait_fn:ident),* $(,)?) => {
+        $(
+            fn $trait_fn<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.$to().$trait_fn(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for FirstElementDeserializer<'_, 'de> {
+    type Error = PathDeserializationError;
+
+    forward_first_element!(segment:
+        deserialize_any,
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_identifier,
+        deserialize_ignored_any,
+    );
+    forward_first_element!(value: deserialize_seq, deserialize_map);
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.segment().deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.segment().deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.segment().deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value().deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value().deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value().deserialize_struct(name, fields, visitor)
+    }
+}
+
+// ==============================================================================
+// SegmentDeserializer
+// ==============================================================================
+
+macro_rules! parse_segment {
+    ($trait_fn:ident, $visit_fn:ident, $ty:literal) => {
+        fn $trait_fn<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: Visitor<'de>,
+        {
+            let value = self.decoded()?;
+            let v = match value.parse() {
+                Ok(v) => v,
+                Err(err) => {
+                    return Err(PathDeserializationError::new(ErrorKind::ParseErrorAtKey {
+                        key: self.key.to_owned(),
+                        value: value.into_owned(),
+                        expected_type: $ty,
+                        message: format!("{err}"),
+                    }));
+                }
+            };
+            visitor.$visit_fn(v)
+        }
+    };
+}
+
+/// A single segment of a parameter, still percent-encoded.
+struct SegmentDeserializer<'de> {
+    key: &'de str,
+    raw: &'de str,
+    lossy_utf8: bool,
+}
+
+impl<'de> SegmentDeserializer<'de> {
+    fn decoded(&self) -> Result<Cow<'de, str>, PathDeserializationError> {
+        let decoded = percent_encoding::percent_decode_str(self.raw);
+        if self.lossy_utf8 {
+            return Ok(decoded.decode_utf8_lossy());
+        }
+        decoded.decode_utf8().map_err(|_| {
+            PathDeserializationError::new(ErrorKind::InvalidUtf8InPathParam {
+                key: self.key.to_owned(),
+            })
+        })
+    }
+
+    fn decoded_bytes(&self) -> Cow<'de, [u8]> {
+        percent_encoding::percent_decode_str(self.raw).into()
+    }
+}
+
+impl<'de> Deserializer<'de> for SegmentDeserializer<'de> {
+    type Error = PathDeserializationError;
+
+    unsupported_type!(deserialize_map);
+    unsupported_type!(deserialize_seq);
+    unsupported_type!(deserialize_identifier);
+
+    parse_segment!(deserialize_bool, visit_bool, "bool");
+    parse_segment!(deserialize_i8, visit_i8, "i8");
+    parse_segment!(deserialize_i16, visit_i16, "i16");
+    parse_segment!(deserialize_i32, visit_i32, "i32");
+    parse_segment!(deserialize_i64, visit_i64, "i64");
+    parse_segment!(deserialize_i128, visit_i128, "i128");
+    parse_segment!(deserialize_u8, visit_u8, "u8");
+    parse_segment!(deserialize_u16, visit_u16, "u16");
+    parse_segment!(deserialize_u32, visit_u32, "u32");
+    parse_segment!(deserialize_u64, visit_u64, "u64");
+    parse_segment!(deserialize_u128, visit_u128, "u128");
+    parse_segment!(deserialize_f32, visit_f32, "f32");
+    parse_segment!(deserialize_f64, visit_f64, "f64");
+    parse_segment!(deserialize_string, visit_string, "String");
+    parse_segment!(deserialize_char, visit_char, "char");
+
+    fn deserialize_any<V>(self, v: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(v)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let value = self.decoded()?;
+        visitor
+            .visit_str(&value)
+            .map_err(|e: PathDeserializationError| {
+                if let ErrorKind::Message(message) = &e.kind {
+                    PathDeserializationError::new(ErrorKind::DeserializeError {
+                        key: self.key.to_owned(),
+                        value: String::from(value.as_ref()),
+                        message: message.to_owned(),
+                    })
+                } else {
+                    e
+                }
+            })
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bytes(&self.decoded_bytes())
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_byte_buf(self.decoded_bytes().into_owned())
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(PathDeserializationError::unsupported_type(type_name::<
+            V::Value,
+        >()))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(PathDeserializationError::unsupported_type(type_name::<
+            V::Value,
+        >()))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(PathDeserializationError::unsupported_type(type_name::<
+            V::Value,
+        >()))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        StringDeserializer::<PathDeserializationError>::new(self.decoded()?.into_owned())
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
 // ==============================================================================
 // KeyOrIdx
 // ==============================================================================
@@ -0,0 +1,192 @@
+// ==============================================================================
+// PathParam<N, T> Extractor
+// ==============================================================================
+//
+// Extracts one named parameter through `FromStr` instead of serde, so ID
+// newtypes (ULIDs, slugs, …) that only implement `FromStr` don't need a
+// `Deserialize` wrapper. The name comes from a marker type, since `&str`
+// const generics aren't stable.
+
+use axum_core::extract::FromRequestParts;
+use http::request::Parts;
+use std::{
+    any::type_name,
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use super::{
+    path::{
+        ErrorKind, FailedToDeserializePathParams, MissingPathParams, PathDeserializationError,
+        PathRejection, WayfindUrlParams,
+    },
+    rejection::InstalledFormatter,
+};
+
+/// Names the parameter a [`PathParam`] extracts.
+///
+/// Implement it on a marker type, or declare one with [`param_name!`]:
+///
+/// ```rust
+/// use axum_wayfind::extract::path_param::ParamName;
+///
+/// struct UserId;
+///
+/// impl ParamName for UserId {
+///     const NAME: &'static str = "user_id";
+/// }
+/// ```
+///
+/// [`param_name!`]: crate::param_name
+pub trait ParamName {
+    /// The parameter name, as written in the route template.
+    const NAME: &'static str;
+}
+
+/// Declare a marker type implementing [`ParamName`].
+///
+/// ```rust
+/// axum_wayfind::param_name!(pub UserId = "user_id");
+/// ```
+#[macro_export]
+macro_rules! param_name {
+    ($vis:vis $marker:ident = $name:literal) => {
+        #[derive(Clone, Copy, Debug)]
+        $vis struct $marker;
+
+        impl $crate::extract::path_param::ParamName for $marker {
+            const NAME: &'static str = $name;
+        }
+    };
+}
+
+/// Extractor for a single named path parameter, parsed with [`FromStr`].
+///
+/// The parameter is percent-decoded and then parsed; serde is not involved,
+/// so `T` only needs `FromStr`. Parse failures are reported as
+/// [`ErrorKind::ParseErrorAtKey`], like a failing `Path` struct field.
+///
+/// ```rust,no_run
+/// use axum_wayfind::{Router, extract::PathParam};
+/// use axum::routing::get;
+/// use std::str::FromStr;
+///
+/// struct Slug(String);
+///
+/// impl FromStr for Slug {
+///     type Err = &'static str;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         if s.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+///             Ok(Self(s.to_owned()))
+///         } else {
+///             Err("not a slug")
+///         }
+///     }
+/// }
+///
+/// axum_wayfind::param_name!(SlugParam = "slug");
+///
+/// async fn handler(slug: PathParam<SlugParam, Slug>) {
+///     println!("slug: {}", slug.0);
+/// }
+///
+/// let app = Router::new().route("/posts/{slug}", get(handler));
+/// # let _: Router = app;
+/// ```
+pub struct PathParam<N, T> {
+    value: T,
+    name: PhantomData<fn() -> N>,
+}
+
+impl<N, T> PathParam<N, T> {
+    /// Unwrap the parsed value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<N, T> Deref for PathParam<N, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<N, T> DerefMut for PathParam<N, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<N, T> fmt::Debug for PathParam<N, T>
+where
+    N: ParamName,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PathParam")
+            .field("name", &N::NAME)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<N, T, S> FromRequestParts<S> for PathParam<N, T>
+where
+    N: ParamName,
+    T: FromStr + Send,
+    T::Err: fmt::Display,
+    S: Send + Sync,
+{
+    type Rejection = PathRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let formatter = || parts.extensions.get::<InstalledFormatter>().cloned();
+        let reject = |kind| {
+            PathRejection::FailedToDeserializePathParams(
+                FailedToDeserializePathParams::new(PathDeserializationError::new(kind))
+                    .with_formatter(formatter()),
+            )
+        };
+
+        let params = parts.extensions.get::<WayfindUrlParams>().ok_or_else(|| {
+            PathRejection::MissingPathParams(MissingPathParams {
+                formatter: formatter(),
+            })
+        })?;
+
+        let param = params
+            .params()
+            .iter()
+            .find(|param| param.key() == N::NAME)
+            .ok_or_else(|| {
+                reject(ErrorKind::MissingParam {
+                    key: N::NAME.to_owned(),
+                })
+            })?;
+
+        let decoded = param.decoded().ok_or_else(|| {
+            reject(ErrorKind::InvalidUtf8InPathParam {
+                key: N::NAME.to_owned(),
+            })
+        })?;
+
+        let value = decoded.parse().map_err(|err: T::Err| {
+            reject(ErrorKind::ParseErrorAtKey {
+                key: N::NAME.to_owned(),
+                value: decoded.to_owned(),
+                expected_type: type_name::<T>(),
+                message: err.to_string(),
+            })
+        })?;
+
+        Ok(Self {
+            value,
+            name: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,210 @@
+// ==============================================================================
+// RawPathParams Extractor
+// ==============================================================================
+//
+// Gives handlers the captured path parameters exactly as they appeared in the
+// request URI, alongside their percent-decoded form and in template order.
+// Unlike `Path`, nothing goes through serde and a parameter that isn't valid
+// UTF-8 once decoded doesn't make the others unreadable.
+
+use axum_core::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum_core::response::{IntoResponse, Response};
+use http::{StatusCode, request::Parts};
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+use super::path::{PercentDecodedStr, SharedStr, WayfindUrlParams};
+
+/// Access the captured path parameters in raw and decoded form.
+///
+/// Useful for proxying and signature verification, where the exact bytes of
+/// each parameter as sent by the client matter.
+///
+/// ```rust,no_run
+/// use axum_wayfind::{Router, extract::RawPathParams};
+/// use axum::routing::get;
+///
+/// async fn handler(params: RawPathParams) {
+///     for param in &params {
+///         // For `/files/a%2Fb`: key "name", raw "a%2Fb", decoded "a/b".
+///         println!("{} = {} ({:?})", param.key(), param.raw(), param.decoded());
+///     }
+/// }
+///
+/// let app = Router::new().route("/files/{name}", get(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone, Debug)]
+pub struct RawPathParams(Arc<[RawPathParam]>);
+
+impl RawPathParams {
+    /// Iterate over the parameters in template order.
+    pub fn iter(&self) -> std::slice::Iter<'_, RawPathParam> {
+        self.0.iter()
+    }
+
+    /// Look up a parameter by name.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&RawPathParam> {
+        self.0.iter().find(|param| param.key() == key)
+    }
+
+    /// The number of captured parameters.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no parameters were captured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a RawPathParams {
+    type Item = &'a RawPathParam;
+    type IntoIter = std::slice::Iter<'a, RawPathParam>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> FromRequestParts<S> for RawPathParams
+where
+    S: Send + Sync,
+{
+    type Rejection = RawPathParamsRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<WayfindUrlParams>()
+            .map(|params| Self(Arc::clone(params.params())))
+            .ok_or(RawPathParamsRejection)
+    }
+}
+
+// Also implement OptionalFromRequestParts so `Option<RawPathParams>` works.
+impl<S> OptionalFromRequestParts<S> for RawPathParams
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<WayfindUrlParams>()
+            .map(|params| Self(Arc::clone(params.params()))))
+    }
+}
+
+// ==============================================================================
+// RawPathParam
+// ==============================================================================
+
+/// A single captured path parameter.
+#[derive(Clone, Debug)]
+pub struct RawPathParam {
+    pub(crate) key: Arc<str>,
+    raw: SharedStr,
+    /// The decoded form, computed by the first extractor that asks for it.
+    /// `Some(None)` caches that the value isn't valid UTF-8.
+    decoded: OnceLock<Option<PercentDecodedStr>>,
+    /// Whether invalid UTF-8 was replaced rather than rejected, so values
+    /// derived from this one (e.g. wildcard segments) decode the same way.
+    pub(crate) lossy_utf8: bool,
+    /// Whether the template captured this as a `{*wildcard}`.
+    pub(crate) wildcard: bool,
+}
+
+impl RawPathParam {
+    /// Capture a parameter. Decoding is deferred until an extractor needs
+    /// the decoded form.
+    pub(crate) const fn new(key: Arc<str>, raw: SharedStr, lossy_utf8: bool, wildcard: bool) -> Self {
+        Self {
+            key,
+            raw,
+            decoded: OnceLock::new(),
+            lossy_utf8,
+            wildcard,
+        }
+    }
+
+    /// The decoded form, percent-decoding on first use.
+    ///
+    /// The result is cached in the parameter, which the request extension
+    /// shares with every extractor, so each value is decoded at most once
+    /// per request.
+    pub(crate) fn decoded_str(&self) -> Option<&PercentDecodedStr> {
+        self.decoded
+            .get_or_init(|| PercentDecodedStr::decode(&self.raw, self.lossy_utf8))
+            .as_ref()
+    }
+
+    /// The parameter name from the route template.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The value exactly as it appeared in the request URI, still
+    /// percent-encoded.
+    #[must_use]
+    pub fn raw(&self) -> &str {
+        self.raw.as_str()
+    }
+
+    /// The percent-decoded value, or `None` if the decoded bytes are not
+    /// valid UTF-8.
+    ///
+    /// When the router decodes lossily (see
+    /// [`Router::lossy_utf8_path_params`](crate::Router::lossy_utf8_path_params)),
+    /// this is always `Some`, with invalid sequences replaced by U+FFFD.
+    #[must_use]
+    pub fn decoded(&self) -> Option<&str> {
+        self.decoded_str().map(PercentDecodedStr::as_str)
+    }
+
+    /// The exact percent-decoded bytes, whether or not they are valid UTF-8.
+    #[must_use]
+    pub fn decoded_bytes(&self) -> Cow<'_, [u8]> {
+        percent_encoding::percent_decode(self.raw.as_bytes()).into()
+    }
+}
+
+// ==============================================================================
+// Rejection
+// ==============================================================================
+
+/// Rejection for [`RawPathParams`] — returned when the request was not
+/// routed through an `axum-wayfind` route (e.g. it reached the fallback).
+#[derive(Debug)]
+pub struct RawPathParamsRejection;
+
+impl IntoResponse for RawPathParamsRejection {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "No path parameters found",
+        )
+            .into_response()
+    }
+}
+
+impl fmt::Display for RawPathParamsRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No path parameters found")
+    }
+}
+
+impl std::error::Error for RawPathParamsRejection {}
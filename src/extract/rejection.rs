@@ -0,0 +1,253 @@
+// ==============================================================================
+// Rejection Formatting
+// ==============================================================================
+//
+// `Path` and `MatchedPath` rejections render as plain text by default, like
+// axum's. A router can install a `RejectionFormatter` instead; we carry it to
+// the extractors as a request extension, and they attach it to the rejection
+// they return so `into_response` can use it.
+
+use std::{fmt, sync::Arc};
+
+use axum_core::response::{IntoResponse, Response};
+use http::{StatusCode, header};
+use serde_json::{Map, Value};
+
+use super::path::{ErrorKind, FailedToDeserializePathParams};
+#[cfg(feature = "validation")]
+use super::validated_path::FailedToValidatePathParams;
+
+/// Renders the rejections of the [`Path`](super::Path) and
+/// [`MatchedPath`](super::MatchedPath) extractors as responses.
+///
+/// Install one with
+/// [`Router::rejection_formatter`](crate::Router::rejection_formatter).
+/// [`ProblemJson`] is provided for RFC 9457 `application/problem+json`
+/// responses.
+pub trait RejectionFormatter: Send + Sync + 'static {
+    /// Build the response for `rejection`.
+    fn format(&self, rejection: &Rejection<'_>) -> Response;
+}
+
+/// An extractor rejection handed to a [`RejectionFormatter`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Rejection<'a> {
+    /// The path parameters couldn't be deserialized by `Path`.
+    FailedToDeserializePathParams(&'a FailedToDeserializePathParams),
+    /// `Path` was used on a request without path parameters.
+    MissingPathParams,
+    /// `MatchedPath` was used on a request no route matched.
+    MissingMatchedPath,
+    /// The parameters deserialized but failed `ValidatedPath` validation.
+    #[cfg(feature = "validation")]
+    FailedToValidatePathParams(&'a FailedToValidatePathParams),
+}
+
+impl Rejection<'_> {
+    /// The status code of the default plain-text response.
+    #[must_use]
+    pub const fn status(&self) -> StatusCode {
+        match self {
+            Self::FailedToDeserializePathParams(inner) => inner.status(),
+            Self::MissingPathParams | Self::MissingMatchedPath => StatusCode::INTERNAL_SERVER_ERROR,
+            #[cfg(feature = "validation")]
+            Self::FailedToValidatePathParams(inner) => inner.status(),
+        }
+    }
+
+    /// The body of the default plain-text response.
+    #[must_use]
+    pub fn body_text(&self) -> String {
+        match self {
+            Self::FailedToDeserializePathParams(inner) => inner.body_text(),
+            Self::MissingPathParams => "No path parameters found".to_owned(),
+            Self::MissingMatchedPath => "Matched path is not available".to_owned(),
+            #[cfg(feature = "validation")]
+            Self::FailedToValidatePathParams(inner) => inner.body_text(),
+        }
+    }
+
+    /// The structured deserialization error, if there is one.
+    #[must_use]
+    pub const fn kind(&self) -> Option<&ErrorKind> {
+        match self {
+            Self::FailedToDeserializePathParams(inner) => Some(inner.kind()),
+            Self::MissingPathParams | Self::MissingMatchedPath => None,
+            #[cfg(feature = "validation")]
+            Self::FailedToValidatePathParams(_) => None,
+        }
+    }
+
+    /// The per-key validation errors, each a
+    /// [`ErrorKind::ParseErrorAtKey`], if validation failed.
+    #[cfg(feature = "validation")]
+    #[must_use]
+    pub fn validation_errors(&self) -> Option<&[ErrorKind]> {
+        match self {
+            Self::FailedToValidatePathParams(inner) => Some(inner.errors().errors()),
+            _ => None,
+        }
+    }
+}
+
+// ==============================================================================
+// ProblemJson
+// ==============================================================================
+
+/// Renders rejections as RFC 9457 `application/problem+json`.
+///
+/// The problem `type` is `about:blank`, so `title` is the status code's
+/// reason phrase and `detail` is the plain-text message. The fields of the
+/// underlying [`ErrorKind`] (`key`, `index`, `value`, `expected_type`,
+/// `message`, …) are added as extension members. For a `Path<Params>` whose
+/// `id: u32` field fails to parse:
+///
+/// ```json
+/// {
+///   "type": "about:blank",
+///   "title": "Bad Request",
+///   "status": 400,
+///   "detail": "Invalid URL: Cannot parse `id` with value `abc` to a `u32`: invalid digit found in string",
+///   "key": "id",
+///   "value": "abc",
+///   "expected_type": "u32",
+///   "message": "invalid digit found in string"
+/// }
+/// ```
+///
+/// A lone scalar such as `Path<u32>` fails with axum's keyless
+/// [`ErrorKind::ParseError`], so its problem has no `key` member.
+///
+/// Validation failures from `ValidatedPath` (with the `validation` feature)
+/// list one such object per failed key under `errors` instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProblemJson;
+
+impl RejectionFormatter for ProblemJson {
+    fn format(&self, rejection: &Rejection<'_>) -> Response {
+        let status = rejection.status();
+
+        let mut problem = Map::new();
+        problem.insert("type".to_owned(), "about:blank".into());
+        problem.insert(
+            "title".to_owned(),
+            status.canonical_reason().unwrap_or_default().into(),
+        );
+        problem.insert("status".to_owned(), status.as_u16().into());
+        problem.insert("detail".to_owned(), rejection.body_text().into());
+        if let Some(kind) = rejection.kind() {
+            insert_error_kind(&mut problem, kind);
+        }
+        #[cfg(feature = "validation")]
+        if let Some(errors) = rejection.validation_errors() {
+            let errors = errors
+                .iter()
+                .map(|kind| {
+                    let mut error = Map::new();
+                    insert_error_kind(&mut error, kind);
+                    Value::Object(error)
+                })
+                .collect();
+            problem.insert("errors".to_owned(), Value::Array(errors));
+        }
+
+        (
+            status,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Value::Object(problem).to_string(),
+        )
+            .into_response()
+    }
+}
+
+/// Add the fields of `kind` to `problem` as extension members.
+fn insert_error_kind(problem: &mut Map<String, Value>, kind: &ErrorKind) {
+    let mut insert = |name: &str, value: Value| {
+        problem.insert(name.to_owned(), value);
+    };
+
+    match kind {
+        ErrorKind::WrongNumberOfParameters { got, expected } => {
+            insert("got", (*got).into());
+            insert("expected", (*expected).into());
+        }
+        ErrorKind::ParseErrorAtKey {
+            key,
+            value,
+            expected_type,
+            message,
+        } => {
+            insert("key", key.as_str().into());
+            insert("value", value.as_str().into());
+            insert("expected_type", (*expected_type).into());
+            insert("message", message.as_str().into());
+        }
+        ErrorKind::ParseErrorAtIndex {
+            index,
+            value,
+            expected_type,
+            message,
+        } => {
+            insert("index", (*index).into());
+            insert("value", value.as_str().into());
+            insert("expected_type", (*expected_type).into());
+            insert("message", message.as_str().into());
+        }
+        ErrorKind::ParseError {
+            value,
+            expected_type,
+            message,
+        } => {
+            insert("value", value.as_str().into());
+            insert("expected_type", (*expected_type).into());
+            insert("message", message.as_str().into());
+        }
+        ErrorKind::InvalidUtf8InPathParam { key } | ErrorKind::MissingParam { key } => {
+            insert("key", key.as_str().into());
+        }
+        ErrorKind::DeserializeError {
+            key,
+            value,
+            message,
+        } => {
+            insert("key", key.as_str().into());
+            insert("value", value.as_str().into());
+            insert("message", message.as_str().into());
+        }
+        // Nothing beyond `detail` worth exposing.
+        ErrorKind::UnsupportedType { .. } | ErrorKind::Message(_) => {}
+    }
+}
+
+// ==============================================================================
+// InstalledFormatter — the extension type we insert during dispatch
+// ==============================================================================
+
+/// The formatter installed on the router, stored as a request extension and
+/// carried by rejections.
+#[derive(Clone)]
+pub(crate) struct InstalledFormatter(Arc<dyn RejectionFormatter>);
+
+impl InstalledFormatter {
+    pub(crate) fn new<F: RejectionFormatter>(formatter: F) -> Self {
+        Self(Arc::new(formatter))
+    }
+}
+
+impl fmt::Debug for InstalledFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstalledFormatter").finish_non_exhaustive()
+    }
+}
+
+/// Render `rejection` with the installed formatter, or as plain text.
+pub(crate) fn render(
+    formatter: Option<&InstalledFormatter>,
+    rejection: &Rejection<'_>,
+) -> Response {
+    formatter.map_or_else(
+        || (rejection.status(), rejection.body_text()).into_response(),
+        |formatter| formatter.0.format(rejection),
+    )
+}
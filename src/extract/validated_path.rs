@@ -0,0 +1,262 @@
+// ==============================================================================
+// ValidatedPath<T> Extractor
+// ==============================================================================
+//
+// `Path<T>` followed by a `ValidatePath` check, so range, length and format
+// rules on path parameters live next to the type instead of being repeated
+// in every handler. Validation failures are reported per key, in the same
+// shape as a `ParseErrorAtKey` deserialization error.
+
+use axum_core::{
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use http::{StatusCode, request::Parts};
+use serde::de::DeserializeOwned;
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use super::{
+    path::{ErrorKind, Path, PathRejection},
+    rejection::{self, InstalledFormatter, Rejection},
+};
+
+/// Validation run by [`ValidatedPath`] after the parameters deserialize.
+///
+/// ```rust
+/// use axum_wayfind::extract::validated_path::{PathValidationErrors, ValidatePath};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Page {
+///     slug: String,
+///     number: u32,
+/// }
+///
+/// impl ValidatePath for Page {
+///     fn validate(&self) -> Result<(), PathValidationErrors> {
+///         let mut errors = PathValidationErrors::new();
+///         if !(1..=100).contains(&self.number) {
+///             errors.add("number", self.number, "u32", "must be between 1 and 100");
+///         }
+///         if self.slug.len() > 64 {
+///             errors.add("slug", &self.slug, "String", "must be at most 64 bytes");
+///         }
+///         errors.into_result()
+///     }
+/// }
+/// ```
+pub trait ValidatePath {
+    /// Check the deserialized parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns every rule that failed, keyed by parameter name.
+    fn validate(&self) -> Result<(), PathValidationErrors>;
+}
+
+/// Extractor that deserializes path parameters like [`Path`] and then
+/// validates them with [`ValidatePath`].
+///
+/// Deserialization failures are rejected exactly as `Path` rejects them;
+/// validation failures are rejected with `400 Bad Request`, as
+/// [`PathRejection::FailedToValidatePathParams`].
+///
+/// ```rust,no_run
+/// use axum_wayfind::{Router, extract::ValidatedPath};
+/// use axum_wayfind::extract::validated_path::{PathValidationErrors, ValidatePath};
+/// use axum::routing::get;
+///
+/// #[derive(serde::Deserialize)]
+/// struct UserId {
+///     id: u32,
+/// }
+///
+/// impl ValidatePath for UserId {
+///     fn validate(&self) -> Result<(), PathValidationErrors> {
+///         let mut errors = PathValidationErrors::new();
+///         if self.id == 0 {
+///             errors.add("id", self.id, "u32", "must not be zero");
+///         }
+///         errors.into_result()
+///     }
+/// }
+///
+/// async fn handler(ValidatedPath(user): ValidatedPath<UserId>) {
+///     println!("user id: {}", user.id);
+/// }
+///
+/// let app = Router::new().route("/users/{id}", get(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug)]
+pub struct ValidatedPath<T>(pub T);
+
+impl<T> Deref for ValidatedPath<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ValidatedPath<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned + ValidatePath + Send,
+    S: Send + Sync,
+{
+    type Rejection = PathRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+
+        value.validate().map_err(|errors| {
+            PathRejection::FailedToValidatePathParams(FailedToValidatePathParams {
+                errors,
+                formatter: parts.extensions.get::<InstalledFormatter>().cloned(),
+            })
+        })?;
+
+        Ok(Self(value))
+    }
+}
+
+// ==============================================================================
+// PathValidationErrors
+// ==============================================================================
+
+/// The validation rules a set of path parameters failed, keyed by parameter
+/// name.
+///
+/// Each error is an [`ErrorKind::ParseErrorAtKey`], so it renders like a
+/// deserialization failure: ``Cannot parse `id` with value `0` to a `u32`:
+/// must not be zero``.
+#[derive(Debug, Default)]
+pub struct PathValidationErrors {
+    errors: Vec<ErrorKind>,
+}
+
+impl PathValidationErrors {
+    /// Create an empty set of errors.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    /// Record that the parameter `key` with `value` failed a rule.
+    ///
+    /// `expected_type` describes what the parameter should have been and
+    /// `message` which rule it broke.
+    pub fn add(
+        &mut self,
+        key: impl Into<String>,
+        value: impl fmt::Display,
+        expected_type: &'static str,
+        message: impl Into<String>,
+    ) {
+        self.errors.push(ErrorKind::ParseErrorAtKey {
+            key: key.into(),
+            value: value.to_string(),
+            expected_type,
+            message: message.into(),
+        });
+    }
+
+    /// Whether no rule failed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The recorded errors, in the order they were added.
+    #[must_use]
+    pub fn errors(&self) -> &[ErrorKind] {
+        &self.errors
+    }
+
+    /// `Ok(())` if no rule failed, otherwise `Err(self)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` if any error was recorded.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl fmt::Display for PathValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, error) in self.errors.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "; ")?;
+            }
+            error.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PathValidationErrors {}
+
+// ==============================================================================
+// Rejection types
+// ==============================================================================
+
+/// Rejection type for [`ValidatedPath`] if the deserialized parameters
+/// failed validation.
+#[derive(Debug)]
+pub struct FailedToValidatePathParams {
+    errors: PathValidationErrors,
+    formatter: Option<InstalledFormatter>,
+}
+
+impl FailedToValidatePathParams {
+    /// Get a reference to the validation errors.
+    #[must_use]
+    pub const fn errors(&self) -> &PathValidationErrors {
+        &self.errors
+    }
+
+    /// Convert this rejection into the validation errors.
+    #[must_use]
+    pub fn into_errors(self) -> PathValidationErrors {
+        self.errors
+    }
+
+    /// Get the response body text used for this rejection.
+    #[must_use]
+    pub fn body_text(&self) -> String {
+        format!("Invalid URL: {}", self.errors)
+    }
+
+    /// Get the status code used for this rejection.
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub const fn status(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+impl IntoResponse for FailedToValidatePathParams {
+    fn into_response(self) -> Response {
+        rejection::render(
+            self.formatter.as_ref(),
+            &Rejection::FailedToValidatePathParams(&self),
+        )
+    }
+}
+
+impl fmt::Display for FailedToValidatePathParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.errors.fmt(f)
+    }
+}
+
+impl std::error::Error for FailedToValidatePathParams {}
@@ -0,0 +1,325 @@
+// ==============================================================================
+// Route Introspection
+// ==============================================================================
+//
+// Types returned by `Router::explain` and `Router::at`. They describe how a
+// path is routed without dispatching a request, so priority surprises (a
+// wildcard winning over a route you expected to match) can be diagnosed,
+// and routing can be unit-tested without handlers or an HTTP round trip.
+
+use std::{fmt, sync::Arc};
+
+use http::Method;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    extract::{
+        path::{FailedToDeserializePathParams, WayfindUrlParams, deserialize_params},
+        raw_path_params::RawPathParam,
+    },
+    router::RouteId,
+};
+
+// ==============================================================================
+// RouteMatch
+// ==============================================================================
+
+/// The route a path resolves to, as returned by [`Router::at`](crate::Router::at).
+///
+/// Carries the same information a handler would see — the matched template
+/// and the decoded path parameters — plus the methods the route accepts.
+#[derive(Clone, Debug)]
+pub struct RouteMatch {
+    route_id: RouteId,
+    template: Arc<str>,
+    params: WayfindUrlParams,
+    allowed_methods: AllowedMethods,
+}
+
+impl RouteMatch {
+    pub(crate) const fn new(
+        route_id: RouteId,
+        template: Arc<str>,
+        params: WayfindUrlParams,
+        allowed_methods: AllowedMethods,
+    ) -> Self {
+        Self {
+            route_id,
+            template,
+            params,
+            allowed_methods,
+        }
+    }
+
+    /// The identifier of the matched route.
+    #[must_use]
+    pub const fn route_id(&self) -> RouteId {
+        self.route_id
+    }
+
+    /// The original Axum-syntax template, as [`MatchedPath`] would report it.
+    ///
+    /// [`MatchedPath`]: crate::extract::MatchedPath
+    #[must_use]
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// The captured path parameters in raw and decoded form, in template
+    /// order — what [`RawPathParams`](crate::extract::RawPathParams) would
+    /// see.
+    #[must_use]
+    pub fn params(&self) -> &[RawPathParam] {
+        self.params.params()
+    }
+
+    /// Look up a single decoded parameter by name.
+    ///
+    /// Returns `None` if the route has no such parameter or its value is not
+    /// valid UTF-8 once decoded.
+    #[must_use]
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params()
+            .iter()
+            .find(|param| param.key() == name)?
+            .decoded()
+    }
+
+    /// Deserialize the parameters into `T`, exactly as `Path<T>` would.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error the [`Path`](crate::extract::Path) extractor would
+    /// reject the request with.
+    pub fn deserialize<T>(&self) -> Result<T, FailedToDeserializePathParams>
+    where
+        T: DeserializeOwned,
+    {
+        deserialize_params(self.params())
+    }
+
+    /// The HTTP methods the matched route accepts.
+    #[must_use]
+    pub const fn allowed_methods(&self) -> &AllowedMethods {
+        &self.allowed_methods
+    }
+}
+
+// ==============================================================================
+// AllowedMethods
+// ==============================================================================
+
+/// The HTTP methods a route accepts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AllowedMethods {
+    /// Every method is accepted — the route was registered with
+    /// [`any`](axum::routing::any) or as a service.
+    Any,
+    /// Only these methods are accepted, in registration order. `GET`
+    /// routes also list `HEAD`, which axum answers automatically.
+    Only(Vec<Method>),
+}
+
+impl AllowedMethods {
+    /// Whether `method` is accepted.
+    #[must_use]
+    pub fn contains(&self, method: &Method) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Only(methods) => methods.contains(method),
+        }
+    }
+}
+
+// ==============================================================================
+// Explanation
+// ==============================================================================
+
+/// How a request path is routed, as returned by
+/// [`Router::explain`](crate::Router::explain).
+///
+/// Its [`Display`](fmt::Display) impl renders a human-readable report:
+///
+/// ```text
+/// path: /users/me
+/// matched: #1 /users/{id} (wayfind: /users/<id>)
+///   id = me
+/// candidates:
+///   * #1 /users/{id}
+///     #2 /users/{*rest}
+/// ```
+#[derive(Clone, Debug)]
+pub struct Explanation {
+    path: String,
+    matched: Option<ExplainedRoute>,
+    candidates: Vec<Candidate>,
+}
+
+impl Explanation {
+    pub(crate) const fn new(
+        path: String,
+        matched: Option<ExplainedRoute>,
+        candidates: Vec<Candidate>,
+    ) -> Self {
+        Self {
+            path,
+            matched,
+            candidates,
+        }
+    }
+
+    /// The path that was explained.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The route that wins for this path, or `None` if the fallback would
+    /// handle it.
+    #[must_use]
+    pub const fn matched(&self) -> Option<&ExplainedRoute> {
+        self.matched.as_ref()
+    }
+
+    /// Every registered route whose template matches the path on its own,
+    /// in registration order.
+    ///
+    /// When more than one route is listed, wayfind's priority rules decided
+    /// between them; the winner is marked with [`Candidate::is_selected`].
+    #[must_use]
+    pub fn candidates(&self) -> &[Candidate] {
+        &self.candidates
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "path: {}", self.path)?;
+
+        match &self.matched {
+            Some(route) => {
+                writeln!(
+                    f,
+                    "matched: {} {} (wayfind: {})",
+                    route.route_id, route.template, route.wayfind_template
+                )?;
+                for (key, value) in &route.params {
+                    writeln!(f, "  {key} = {value}")?;
+                }
+            }
+            None => writeln!(f, "matched: <fallback>")?,
+        }
+
+        writeln!(f, "candidates:")?;
+        if self.candidates.is_empty() {
+            writeln!(f, "  <none>")?;
+        }
+        for candidate in &self.candidates {
+            let marker = if candidate.selected { '*' } else { ' ' };
+            writeln!(
+                f,
+                "  {marker} {} {}",
+                candidate.route_id, candidate.template
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// ==============================================================================
+// ExplainedRoute
+// ==============================================================================
+
+/// The route selected for a path by [`Router::explain`](crate::Router::explain).
+#[derive(Clone, Debug)]
+pub struct ExplainedRoute {
+    route_id: RouteId,
+    template: Arc<str>,
+    wayfind_template: String,
+    params: Vec<(String, String)>,
+}
+
+impl ExplainedRoute {
+    pub(crate) const fn new(
+        route_id: RouteId,
+        template: Arc<str>,
+        wayfind_template: String,
+        params: Vec<(String, String)>,
+    ) -> Self {
+        Self {
+            route_id,
+            template,
+            wayfind_template,
+            params,
+        }
+    }
+
+    /// The identifier of the matched route.
+    #[must_use]
+    pub const fn route_id(&self) -> RouteId {
+        self.route_id
+    }
+
+    /// The original Axum-syntax template (e.g. `"/users/{id}"`).
+    #[must_use]
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// The template as inserted into wayfind (e.g. `"/users/<id>"`).
+    #[must_use]
+    pub fn wayfind_template(&self) -> &str {
+        &self.wayfind_template
+    }
+
+    /// The captured parameters as `(name, raw value)` pairs, in template
+    /// order.
+    ///
+    /// Values are not percent-decoded, and internal parameters used by the
+    /// nesting infrastructure are included.
+    #[must_use]
+    pub fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+// ==============================================================================
+// Candidate
+// ==============================================================================
+
+/// A route whose template matches the explained path on its own.
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    route_id: RouteId,
+    template: Arc<str>,
+    selected: bool,
+}
+
+impl Candidate {
+    pub(crate) const fn new(route_id: RouteId, template: Arc<str>, selected: bool) -> Self {
+        Self {
+            route_id,
+            template,
+            selected,
+        }
+    }
+
+    /// The identifier of the candidate route.
+    #[must_use]
+    pub const fn route_id(&self) -> RouteId {
+        self.route_id
+    }
+
+    /// The original Axum-syntax template.
+    #[must_use]
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Whether this candidate won and would handle the request.
+    #[must_use]
+    pub const fn is_selected(&self) -> bool {
+        self.selected
+    }
+}
@@ -19,9 +19,86 @@
 
 #![warn(missing_docs)]
 
+/// Compile-time template checks: [`PathFields`](checked::PathFields) and
+/// its derive, used with [`route!`].
+#[cfg(feature = "macros")]
+pub mod checked;
+/// Router-aware CORS: [`CorsPolicy`](cors::CorsPolicy), used with
+/// [`Router::cors`] and [`Router::route_cors`].
+#[cfg(feature = "cors")]
+pub mod cors;
 pub mod extract;
+/// Route introspection types returned by [`Router::explain`] and [`Router::at`].
+pub mod inspect;
+mod methods;
+/// Per-route request metrics: [`MetricsRecorder`](metrics::MetricsRecorder)
+/// and the built-in [`InMemoryMetrics`](metrics::InMemoryMetrics).
+pub mod metrics;
+mod pattern;
+/// Declarative per-route limits: [`RoutePolicy`](policy::RoutePolicy), used
+/// with [`Router::route_policy`].
+pub mod policy;
+mod redirect;
 mod router;
 mod strip_prefix;
 mod syntax;
+/// Typed, compile-time-checked routes: [`WayfindTypedPath`](typed::WayfindTypedPath)
+/// and its derive.
+#[cfg(feature = "typed-routing")]
+pub mod typed;
 
-pub use router::{IntoMakeService, Router};
+pub use router::{IntoMakeService, RouteId, Router, RouterFuture};
+
+/// Check a route template at compile time.
+///
+/// Expands to the template string, after checking its syntax with the same
+/// parser [`Router::route`] uses: a malformed template is a compile error
+/// instead of a panic when the router is built.
+///
+/// ```rust
+/// use axum_wayfind::{Router, route};
+/// use axum::routing::get;
+///
+/// let app: Router = Router::new().route(route!("/files/{name}.{ext}"), get(|| async {}));
+/// ```
+///
+/// ```rust,compile_fail
+/// let path = axum_wayfind::route!("/users/{id");
+/// ```
+///
+/// Pass a [`PathFields`](checked::PathFields) type after the template to
+/// also check that each of its fields is a parameter of the template.
+#[cfg(feature = "macros")]
+pub use axum_wayfind_macros::route;
+
+/// Entry points for the fuzz targets in `fuzz/`. Not public API.
+#[cfg(fuzzing)]
+#[doc(hidden)]
+pub mod __fuzz {
+    /// Translate an Axum-style template to wayfind syntax, or describe why
+    /// it doesn't parse.
+    ///
+    /// # Errors
+    ///
+    /// Returns the syntax error's message.
+    pub fn axum_to_wayfind(path: &str) -> Result<String, String> {
+        crate::syntax::try_axum_to_wayfind(path).map_err(|err| err.to_string())
+    }
+
+    /// Strip `prefix` from `uri`, as nested routers do.
+    #[must_use]
+    pub fn strip_prefix(uri: &http::Uri, prefix: &str) -> Option<http::Uri> {
+        crate::strip_prefix::strip_prefix(uri, prefix)
+    }
+}
+
+/// Items used by code generated by our macros. Not public API.
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::checked::fields_are_params;
+    #[cfg(feature = "typed-routing")]
+    pub use crate::typed::{write_segment, write_wildcard};
+    pub use axum_core::extract::FromRequestParts;
+    pub use http::request::Parts;
+}
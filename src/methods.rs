@@ -0,0 +1,214 @@
+// ==============================================================================
+// Allowed Methods
+// ==============================================================================
+//
+// axum's `MethodRouter` knows which methods it accepts — it precomputes the
+// `Allow` header it sends with 405 responses — but doesn't expose that set
+// publicly. Its `Debug` impl does include the precomputed header, so we read
+// it from there — once, when the route is registered, and the router keeps
+// the result with the route's metadata. Cargo.toml holds axum to `~0.8.8`,
+// the release this format was checked against, the unit tests below pin the
+// format itself, and an unrecognised format panics at registration rather
+// than silently reporting no methods.
+
+use axum::routing::MethodRouter;
+use http::{HeaderValue, Method};
+
+use crate::{inspect::AllowedMethods, metrics::OTHER_METHOD};
+
+/// Determine which HTTP methods a `MethodRouter` accepts.
+///
+/// # Panics
+///
+/// If axum's `Debug` output no longer has the shape this reads.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+#[allow(clippy::panic)] // Intentional: a silently empty method set would break `Allow` headers.
+pub(crate) fn allowed_methods<S>(method_router: &MethodRouter<S>) -> AllowedMethods {
+    let debug = format!("{method_router:?}");
+    let unrecognised = || -> ! {
+        panic!(
+            "couldn't read the allowed methods from axum's `MethodRouter` debug output; \
+             this axum version isn't supported"
+        )
+    };
+
+    let Some((_, allow_header)) = debug.split_once("allow_header: ") else {
+        unrecognised()
+    };
+
+    // `any` and `any_service` skip the `Allow` header: every method is
+    // accepted.
+    if allow_header.starts_with("Skip") {
+        return AllowedMethods::Any;
+    }
+
+    // `None` means no methods yet.
+    if allow_header.starts_with("None") {
+        return AllowedMethods::Only(Vec::new());
+    }
+
+    // Otherwise the header is `Bytes(b"GET,HEAD")`. Merging method routers
+    // can repeat a method, so deduplicate.
+    let Some((value, _)) = allow_header
+        .strip_prefix("Bytes(b\"")
+        .and_then(|rest| rest.split_once('"'))
+    else {
+        unrecognised()
+    };
+
+    let mut methods = Vec::new();
+    for method in value.split(',') {
+        let Ok(method) = method.parse::<Method>() else {
+            unrecognised()
+        };
+        if !methods.contains(&method) {
+            methods.push(method);
+        }
+    }
+
+    AllowedMethods::Only(methods)
+}
+
+/// The methods a whole router accepts, for `OPTIONS *`: the union of its
+/// routes' methods, or every standard method if any route accepts all.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn server_allowed_methods<'a>(
+    routes: impl IntoIterator<Item = &'a AllowedMethods>,
+) -> Vec<Method> {
+    let mut methods = Vec::new();
+    for route in routes {
+        match route {
+            AllowedMethods::Any => {
+                return vec![
+                    Method::GET,
+                    Method::HEAD,
+                    Method::POST,
+                    Method::PUT,
+                    Method::PATCH,
+                    Method::DELETE,
+                    Method::OPTIONS,
+                    Method::TRACE,
+                ];
+            }
+            AllowedMethods::Only(route_methods) => {
+                for method in route_methods {
+                    if !methods.contains(method) {
+                        methods.push(method.clone());
+                    }
+                }
+            }
+        }
+    }
+    methods
+}
+
+/// How metrics and spans label `method`: its name if it's a standard
+/// method, or [`OTHER_METHOD`], so clients sending made-up methods can't
+/// create a series per method.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn method_label(method: &Method) -> &'static str {
+    match method.as_str() {
+        "GET" => "GET",
+        "HEAD" => "HEAD",
+        "POST" => "POST",
+        "PUT" => "PUT",
+        "PATCH" => "PATCH",
+        "DELETE" => "DELETE",
+        "OPTIONS" => "OPTIONS",
+        "TRACE" => "TRACE",
+        "CONNECT" => "CONNECT",
+        _ => OTHER_METHOD,
+    }
+}
+
+/// The `Allow` header for an automatic `OPTIONS` response: `methods`, in
+/// order, plus `OPTIONS` itself.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+#[allow(clippy::expect_used)] // Invariant: method names are valid header values.
+pub(crate) fn allow_header(methods: &[Method]) -> HeaderValue {
+    let mut value = methods
+        .iter()
+        .map(Method::as_str)
+        .collect::<Vec<_>>()
+        .join(",");
+    if !methods.contains(&Method::OPTIONS) {
+        if !value.is_empty() {
+            value.push(',');
+        }
+        value.push_str(Method::OPTIONS.as_str());
+    }
+
+    HeaderValue::try_from(value).expect("method names are valid header values")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)] // Tests panic on failure by design.
+
+    use super::*;
+    use axum::routing::{any, get, post};
+
+    #[test]
+    fn get_includes_head() {
+        let mr: MethodRouter = get(|| async {});
+        assert_eq!(
+            allowed_methods(&mr),
+            AllowedMethods::Only(vec![Method::GET, Method::HEAD])
+        );
+    }
+
+    #[test]
+    fn merged_methods_in_registration_order() {
+        let mr: MethodRouter = post(|| async {}).merge(get(|| async {}));
+        assert_eq!(
+            allowed_methods(&mr),
+            AllowedMethods::Only(vec![Method::POST, Method::GET, Method::HEAD])
+        );
+    }
+
+    #[test]
+    fn any_accepts_everything() {
+        let mr: MethodRouter = any(|| async {});
+        assert_eq!(allowed_methods(&mr), AllowedMethods::Any);
+    }
+
+    #[test]
+    fn empty_method_router() {
+        let mr: MethodRouter = MethodRouter::new();
+        assert_eq!(allowed_methods(&mr), AllowedMethods::Only(Vec::new()));
+    }
+
+    #[test]
+    fn non_standard_methods_share_a_label() {
+        assert_eq!(method_label(&Method::PATCH), "PATCH");
+        let custom = Method::from_bytes(b"PURGE").expect("valid method");
+        assert_eq!(method_label(&custom), OTHER_METHOD);
+    }
+
+    #[test]
+    fn allow_header_adds_options_once() {
+        assert_eq!(
+            allow_header(&[Method::GET, Method::HEAD]),
+            "GET,HEAD,OPTIONS"
+        );
+        assert_eq!(allow_header(&[Method::OPTIONS]), "OPTIONS");
+        assert_eq!(allow_header(&[]), "OPTIONS");
+    }
+
+    #[test]
+    fn server_methods_are_the_union() {
+        let get_mr: MethodRouter = get(|| async {});
+        let post_mr: MethodRouter = post(|| async {}).merge(get(|| async {}));
+        let get_methods = allowed_methods(&get_mr);
+        assert_eq!(
+            server_allowed_methods([&get_methods, &allowed_methods(&post_mr)]),
+            vec![Method::GET, Method::HEAD, Method::POST]
+        );
+
+        let any_mr: MethodRouter = any(|| async {});
+        assert!(
+            server_allowed_methods([&get_methods, &allowed_methods(&any_mr)])
+                .contains(&Method::TRACE)
+        );
+    }
+}
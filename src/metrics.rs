@@ -0,0 +1,371 @@
+// ==============================================================================
+// Per-route Metrics
+// ==============================================================================
+//
+// `Router::call` knows the matched route before the handler runs, so it can
+// label each request with its template instead of the raw path — the raw
+// path would give every user ID its own time series. A router can install a
+// `MetricsRecorder`; dispatch starts a `PendingRequest` and `RouterFuture`
+// finishes it once the response is ready. A request whose future is dropped
+// first is recorded as cancelled when the `PendingRequest` is dropped.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use http::{Method, StatusCode};
+
+use crate::methods;
+
+/// The route label of requests handled by a fallback — the router's own,
+/// or a nested router's.
+///
+/// Templates always start with `/`, so this never collides with one.
+pub const UNMATCHED: &str = "unmatched";
+
+/// The method label of requests with a non-standard method.
+///
+/// Methods are client-chosen, so labelling each one separately would let
+/// clients create unbounded series. The name follows OpenTelemetry's HTTP
+/// semantic conventions.
+pub const OTHER_METHOD: &str = "_OTHER";
+
+/// The status label [`InMemoryMetrics`] gives requests that were cancelled
+/// before their response was ready.
+pub const CANCELLED: &str = "cancelled";
+
+/// Receives one [`RequestMetrics`] per request a router dispatches.
+///
+/// Install one with
+/// [`Router::metrics_recorder`](crate::Router::metrics_recorder).
+/// [`InMemoryMetrics`] is provided for scraping from a handler; implement
+/// this trait to forward to a metrics library instead.
+pub trait MetricsRecorder: Send + Sync + 'static {
+    /// Record a finished or cancelled request.
+    fn record(&self, request: &RequestMetrics<'_>);
+}
+
+// ==============================================================================
+// RequestMetrics
+// ==============================================================================
+
+/// What a router reports about a request to its [`MetricsRecorder`].
+#[derive(Clone, Copy, Debug)]
+pub struct RequestMetrics<'a> {
+    route: &'a str,
+    method: &'static str,
+    status: Option<StatusCode>,
+    latency: Duration,
+}
+
+impl RequestMetrics<'_> {
+    /// The Axum-syntax template of the matched route, as
+    /// [`MatchedPath`](crate::extract::MatchedPath) would report it, or
+    /// [`UNMATCHED`] if a fallback handled the request.
+    #[must_use]
+    pub const fn route(&self) -> &str {
+        self.route
+    }
+
+    /// The request method, or [`OTHER_METHOD`] for a non-standard one.
+    #[must_use]
+    pub const fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The response status, or `None` if the request was cancelled: its
+    /// future was dropped before the response was ready, as when the client
+    /// disconnects or an outer timeout fires.
+    #[must_use]
+    pub const fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+
+    /// Time from dispatch until the response was ready or the request was
+    /// cancelled, not counting the time taken to stream a response body.
+    #[must_use]
+    pub const fn latency(&self) -> Duration {
+        self.latency
+    }
+}
+
+// ==============================================================================
+// InMemoryMetrics
+// ==============================================================================
+
+/// A [`MetricsRecorder`] that aggregates request counts and latencies per
+/// route, method and status in memory.
+///
+/// Clones share the same counters, so keep one to read them from a handler.
+/// The [`Display`](fmt::Display) impl renders them in the Prometheus text
+/// format:
+///
+/// ```rust
+/// use axum::routing::get;
+/// use axum_wayfind::{Router, metrics::InMemoryMetrics};
+///
+/// let metrics = InMemoryMetrics::new();
+/// let scrape = metrics.clone();
+///
+/// let app: Router = Router::new()
+///     .route("/users/{id}", get(|| async { "user" }))
+///     .route("/metrics", get(move || async move { scrape.to_string() }))
+///     .metrics_recorder(metrics);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryMetrics {
+    routes: Arc<Mutex<HashMap<Arc<str>, Series>>>,
+}
+
+/// A route's counters, by method label and status (`None` if cancelled).
+type Series = HashMap<(&'static str, Option<StatusCode>), Counters>;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Counters {
+    count: u64,
+    latency_total: Duration,
+    latency_max: Duration,
+}
+
+impl Counters {
+    /// Count one more request that took `latency`.
+    fn add(&mut self, latency: Duration) {
+        self.count += 1;
+        self.latency_total += latency;
+        self.latency_max = self.latency_max.max(latency);
+    }
+}
+
+impl InMemoryMetrics {
+    /// Create an empty recorder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The counters recorded so far, sorted by route, method and status.
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<RouteMetrics> {
+        let mut snapshot: Vec<RouteMetrics> = self
+            .routes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .flat_map(|(route, series)| {
+                series
+                    .iter()
+                    .map(|(&(method, status), counters)| RouteMetrics {
+                        route: Arc::clone(route),
+                        method,
+                        status,
+                        counters: *counters,
+                    })
+            })
+            .collect();
+        snapshot.sort_by(|a, b| {
+            (a.route(), a.method, a.status).cmp(&(b.route(), b.method, b.status))
+        });
+        snapshot
+    }
+}
+
+impl MetricsRecorder for InMemoryMetrics {
+    fn record(&self, request: &RequestMetrics<'_>) {
+        let mut routes = self.routes.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Only a route's first request allocates its label.
+        if !routes.contains_key(request.route) {
+            routes.insert(Arc::from(request.route), HashMap::new());
+        }
+        if let Some(series) = routes.get_mut(request.route) {
+            series
+                .entry((request.method, request.status))
+                .or_default()
+                .add(request.latency);
+        }
+    }
+}
+
+/// Renders the counters as Prometheus text: an
+/// `http_server_requests_total` counter and an
+/// `http_server_request_duration_seconds` summary, labelled by `route`,
+/// `method` and `status` ([`CANCELLED`] for cancelled requests).
+impl fmt::Display for InMemoryMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let snapshot = self.snapshot();
+
+        writeln!(f, "# TYPE http_server_requests_total counter")?;
+        for entry in &snapshot {
+            writeln!(
+                f,
+                "http_server_requests_total{{{}}} {}",
+                Labels(entry),
+                entry.count()
+            )?;
+        }
+
+        writeln!(f, "# TYPE http_server_request_duration_seconds summary")?;
+        for entry in &snapshot {
+            writeln!(
+                f,
+                "http_server_request_duration_seconds_sum{{{}}} {}",
+                Labels(entry),
+                entry.latency_total().as_secs_f64()
+            )?;
+            writeln!(
+                f,
+                "http_server_request_duration_seconds_count{{{}}} {}",
+                Labels(entry),
+                entry.count()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The Prometheus label set of an entry, with values escaped.
+struct Labels<'a>(&'a RouteMetrics);
+
+impl fmt::Display for Labels<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("route=\"")?;
+        for ch in self.0.route().chars() {
+            match ch {
+                '\\' => f.write_str("\\\\")?,
+                '"' => f.write_str("\\\"")?,
+                '\n' => f.write_str("\\n")?,
+                _ => write!(f, "{ch}")?,
+            }
+        }
+        write!(f, "\",method=\"{}\",status=\"", self.0.method())?;
+        match self.0.status() {
+            Some(status) => write!(f, "{}\"", status.as_u16()),
+            None => write!(f, "{CANCELLED}\""),
+        }
+    }
+}
+
+/// The aggregated counters of one route, method and status, as returned by
+/// [`InMemoryMetrics::snapshot`].
+#[derive(Clone, Debug)]
+pub struct RouteMetrics {
+    route: Arc<str>,
+    method: &'static str,
+    status: Option<StatusCode>,
+    counters: Counters,
+}
+
+impl RouteMetrics {
+    /// The route label: a template, or [`UNMATCHED`].
+    #[must_use]
+    pub fn route(&self) -> &str {
+        &self.route
+    }
+
+    /// The request method, or [`OTHER_METHOD`] for non-standard ones.
+    #[must_use]
+    pub const fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The response status, or `None` for cancelled requests.
+    #[must_use]
+    pub const fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+
+    /// The number of requests.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.counters.count
+    }
+
+    /// The summed latency of all requests.
+    #[must_use]
+    pub const fn latency_total(&self) -> Duration {
+        self.counters.latency_total
+    }
+
+    /// The highest latency of any request.
+    #[must_use]
+    pub const fn latency_max(&self) -> Duration {
+        self.counters.latency_max
+    }
+}
+
+// ==============================================================================
+// InstalledRecorder — the recorder held by the router
+// ==============================================================================
+
+/// The recorder installed on the router.
+#[derive(Clone)]
+pub(crate) struct InstalledRecorder(Arc<dyn MetricsRecorder>);
+
+impl InstalledRecorder {
+    pub(crate) fn new<R: MetricsRecorder>(recorder: R) -> Self {
+        Self(Arc::new(recorder))
+    }
+
+    /// Start timing a request. `route` is the matched template, or `None`
+    /// if a fallback handles it.
+    pub(crate) fn start(&self, method: &Method, route: Option<Arc<str>>) -> PendingRequest {
+        PendingRequest {
+            recorder: Some(Arc::clone(&self.0)),
+            method: methods::method_label(method),
+            route,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl fmt::Debug for InstalledRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstalledRecorder").finish_non_exhaustive()
+    }
+}
+
+/// A request being timed, recorded once its response is ready, or as
+/// cancelled if it's dropped first.
+pub(crate) struct PendingRequest {
+    /// Taken once the request is recorded.
+    recorder: Option<Arc<dyn MetricsRecorder>>,
+    method: &'static str,
+    route: Option<Arc<str>>,
+    started: Instant,
+}
+
+impl PendingRequest {
+    pub(crate) fn finish(mut self, status: StatusCode) {
+        self.record(Some(status));
+    }
+
+    fn record(&mut self, status: Option<StatusCode>) {
+        if let Some(recorder) = self.recorder.take() {
+            recorder.record(&RequestMetrics {
+                route: self.route.as_deref().unwrap_or(UNMATCHED),
+                method: self.method,
+                status,
+                latency: self.started.elapsed(),
+            });
+        }
+    }
+}
+
+impl Drop for PendingRequest {
+    fn drop(&mut self) {
+        self.record(None);
+    }
+}
+
+impl fmt::Debug for PendingRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingRequest")
+            .field("method", &self.method)
+            .field("route", &self.route)
+            .finish_non_exhaustive()
+    }
+}
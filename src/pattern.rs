@@ -0,0 +1,135 @@
+// ==============================================================================
+// Template Patterns
+// ==============================================================================
+//
+// Selects registered routes by their Axum-syntax template rather than by
+// request path, for APIs that configure a subset of the route table such as
+// `Router::route_policy`. A pattern is a template with two extra segment
+// forms:
+//
+// - `*` matches exactly one template segment, whatever it contains,
+// - `**` matches any number of template segments, including none.
+//
+// Every other segment must equal the template's segment, so `{id}` only
+// matches a parameter named `id`, and a pattern without globs selects the
+// one template it spells.
+
+/// One `/`-separated segment of a pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    /// Matches this exact template segment.
+    Literal(Box<str>),
+    /// `*`: matches any single segment.
+    One,
+    /// `**`: matches any number of segments.
+    Any,
+}
+
+/// A parsed template pattern.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TemplatePattern {
+    segments: Vec<Segment>,
+}
+
+impl TemplatePattern {
+    /// Parse `pattern`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern doesn't start with `/`, like a template.
+    #[allow(clippy::panic)] // Intentional: invalid patterns are programming errors.
+    pub(crate) fn new(pattern: &str) -> Self {
+        let Some(rest) = pattern.strip_prefix('/') else {
+            panic!("template pattern must start with `/`, got `{pattern}`");
+        };
+
+        let segments = rest
+            .split('/')
+            .map(|segment| match segment {
+                "*" => Segment::One,
+                "**" => Segment::Any,
+                literal => Segment::Literal(literal.into()),
+            })
+            .collect();
+
+        Self { segments }
+    }
+
+    /// Whether `template` is selected by this pattern.
+    pub(crate) fn matches(&self, template: &str) -> bool {
+        let Some(rest) = template.strip_prefix('/') else {
+            return false;
+        };
+        let template: Vec<&str> = rest.split('/').collect();
+
+        matches_segments(&self.segments, &template)
+    }
+}
+
+fn matches_segments(pattern: &[Segment], template: &[&str]) -> bool {
+    let Some((first, pattern_rest)) = pattern.split_first() else {
+        return template.is_empty();
+    };
+
+    match first {
+        Segment::Any => {
+            (0..=template.len()).any(|skip| matches_segments(pattern_rest, &template[skip..]))
+        }
+        Segment::One => template
+            .split_first()
+            .is_some_and(|(_, template_rest)| matches_segments(pattern_rest, template_rest)),
+        Segment::Literal(literal) => {
+            template
+                .split_first()
+                .is_some_and(|(segment, template_rest)| {
+                    *segment == literal.as_ref() && matches_segments(pattern_rest, template_rest)
+                })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, template: &str) -> bool {
+        TemplatePattern::new(pattern).matches(template)
+    }
+
+    #[test]
+    fn literal_pattern_selects_one_template() {
+        assert!(matches("/reports/{*path}", "/reports/{*path}"));
+        assert!(!matches("/reports/{*path}", "/reports/{*rest}"));
+        assert!(!matches("/reports/{*path}", "/reports"));
+        assert!(matches("/", "/"));
+        assert!(!matches("/", "/a"));
+    }
+
+    #[test]
+    fn star_matches_one_segment() {
+        assert!(matches("/users/*", "/users/{id}"));
+        assert!(matches("/users/*", "/users/me"));
+        assert!(matches("/*/posts", "/{user}/posts"));
+        assert!(!matches("/users/*", "/users/{id}/posts"));
+        assert!(!matches("/users/*", "/users"));
+    }
+
+    #[test]
+    fn double_star_matches_any_depth() {
+        assert!(matches("/admin/**", "/admin"));
+        assert!(matches("/admin/**", "/admin/"));
+        assert!(matches("/admin/**", "/admin/users/{id}"));
+        assert!(!matches("/admin/**", "/administrator"));
+        assert!(matches("/**/edit", "/{*path}/edit"));
+        assert!(matches("/**/edit", "/a/b/edit"));
+        assert!(!matches("/**/edit", "/a/b/view"));
+        assert!(matches("/**", "/"));
+    }
+
+    #[test]
+    #[should_panic(expected = "template pattern must start with `/`")]
+    fn pattern_without_leading_slash_panics() {
+        TemplatePattern::new("admin/**");
+    }
+}
@@ -0,0 +1,127 @@
+// ==============================================================================
+// Route Policies
+// ==============================================================================
+//
+// Timeouts, concurrency limits and load shedding declared against the route
+// table with `Router::route_policy`, instead of wrapping each `MethodRouter`
+// by hand. A policy becomes tower layers on the selected routes, baked into
+// their `MethodRouter`s, so it travels through `nest` and `merge` like any
+// other route layer.
+
+use std::time::Duration;
+
+use axum::{BoxError, error_handling::HandleErrorLayer, routing::MethodRouter};
+use http::StatusCode;
+use tower::{
+    ServiceBuilder,
+    limit::GlobalConcurrencyLimitLayer,
+    load_shed::{LoadShedLayer, error::Overloaded},
+    timeout::{TimeoutLayer, error::Elapsed},
+};
+
+/// Limits applied to the routes selected by
+/// [`Router::route_policy`](crate::Router::route_policy).
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use axum_wayfind::policy::RoutePolicy;
+///
+/// // 30s to respond, at most 4 requests at once, and a 503 instead of
+/// // queueing when all 4 are busy.
+/// let policy = RoutePolicy::new()
+///     .timeout(Duration::from_secs(30))
+///     .concurrency_limit(4)
+///     .load_shed();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct RoutePolicy {
+    timeout: Option<Duration>,
+    concurrency_limit: Option<usize>,
+    load_shed: bool,
+}
+
+impl RoutePolicy {
+    /// A policy with no limits.
+    pub const fn new() -> Self {
+        Self {
+            timeout: None,
+            concurrency_limit: None,
+            load_shed: false,
+        }
+    }
+
+    /// Answer `408 Request Timeout` if the handler hasn't responded within
+    /// `timeout`.
+    ///
+    /// Under a concurrency limit, the timeout starts once the request has a
+    /// slot.
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Allow at most `max` requests in flight at once. This is per route,
+    /// shared by all of its methods; further requests wait for a slot.
+    pub const fn concurrency_limit(mut self, max: usize) -> Self {
+        self.concurrency_limit = Some(max);
+        self
+    }
+
+    /// Answer `503 Service Unavailable` instead of waiting when the
+    /// [concurrency limit](Self::concurrency_limit) is reached. Has no effect
+    /// without one.
+    pub const fn load_shed(mut self) -> Self {
+        self.load_shed = true;
+        self
+    }
+
+    /// Wrap `method_router` in this policy's layers.
+    pub(crate) fn apply<S>(&self, method_router: MethodRouter<S>) -> MethodRouter<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let mut method_router = method_router;
+
+        // Innermost first: the timeout covers only the handler, and the limit
+        // guards both, so time spent waiting for a slot doesn't count against
+        // the timeout. Each route gets its own semaphore, shared by its
+        // methods.
+        if let Some(timeout) = self.timeout {
+            method_router = method_router.route_layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(policy_error))
+                    .layer(TimeoutLayer::new(timeout)),
+            );
+        }
+
+        if let Some(max) = self.concurrency_limit {
+            let limit = GlobalConcurrencyLimitLayer::new(max);
+            method_router = if self.load_shed {
+                method_router.route_layer(
+                    ServiceBuilder::new()
+                        .layer(HandleErrorLayer::new(policy_error))
+                        .layer(LoadShedLayer::new())
+                        .layer(limit),
+                )
+            } else {
+                method_router.route_layer(limit)
+            };
+        }
+
+        method_router
+    }
+}
+
+/// The response for a request a policy refused.
+#[allow(clippy::unused_async)] // `HandleErrorLayer` takes an async fn.
+async fn policy_error(err: BoxError) -> StatusCode {
+    if err.is::<Elapsed>() {
+        StatusCode::REQUEST_TIMEOUT
+    } else if err.is::<Overloaded>() {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
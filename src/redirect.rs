@@ -0,0 +1,264 @@
+// ==============================================================================
+// Redirect Routes
+// ==============================================================================
+//
+// `Router::redirect` registers a route answering every request with a
+// redirect, its `Location` built from a target template filled with the
+// parameters the source template captured. Targets are checked when the
+// route is registered, so a renamed endpoint can't redirect to a template
+// whose parameters the old one never captured.
+//
+// Captured values are substituted as they appeared in the request URI,
+// still percent-encoded, so they come out exactly as they went in. That makes
+// them attacker-controlled, so a target has to fix where it points: it starts
+// with `/` or with a scheme and host, and a path whose captures would make it
+// start with `//` or `/\` — which browsers read as another host — is
+// collapsed to a single `/`.
+
+use axum_core::response::{IntoResponse, Response};
+use http::{HeaderValue, StatusCode, header};
+
+use crate::syntax::{self, Part};
+
+/// One piece of a target template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Piece {
+    /// Copied as-is.
+    Static(Box<str>),
+    /// Replaced by the captured value of this parameter or wildcard.
+    Param(Box<str>),
+}
+
+/// A validated redirect target.
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+#[derive(Clone, Debug)]
+pub(crate) struct RedirectTarget {
+    pieces: Vec<Piece>,
+    status: StatusCode,
+}
+
+impl RedirectTarget {
+    /// Check that `to` is a valid target for requests matching `from`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `status` isn't a redirection, if either template is
+    /// invalid, if `to` doesn't start with `/` or with a scheme and host, or
+    /// if `to` uses a parameter `from` doesn't capture.
+    #[allow(clippy::panic)] // Intentional: invalid redirects are programming errors.
+    pub(crate) fn new(from: &str, to: &str, status: StatusCode) -> Self {
+        assert!(
+            status.is_redirection(),
+            "redirect status must be 3xx, got {status}"
+        );
+
+        let captured = syntax::parse(from)
+            .unwrap_or_else(|err| panic!("{err}: `{from}`"))
+            .into_iter()
+            .filter_map(|part| match part {
+                Part::Param(name) | Part::Wildcard(name) => Some(name),
+                Part::Static(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let parts =
+            syntax::parse(to).unwrap_or_else(|err| panic!("invalid redirect target `{to}`: {err}"));
+        assert!(
+            parts.iter().all(|part| matches!(part, Part::Static(_)))
+                || matches!(parts.first(), Some(Part::Static(text)) if fixes_origin(text)),
+            "redirect target `{to}` must start with `/` or with a scheme and host"
+        );
+
+        let pieces = parts
+            .into_iter()
+            .map(|part| match part {
+                Part::Static(text) => {
+                    assert!(
+                        HeaderValue::from_str(text).is_ok(),
+                        "invalid redirect target `{to}`: not a valid `Location` header"
+                    );
+                    Piece::Static(text.into())
+                }
+                Part::Param(name) | Part::Wildcard(name) => {
+                    assert!(
+                        captured.contains(&name),
+                        "redirect target `{to}` uses `{name}`, which `{from}` doesn't capture"
+                    );
+                    Piece::Param(name.into())
+                }
+            })
+            .collect();
+
+        Self { pieces, status }
+    }
+
+    /// The `Location` for a request whose parameters are looked up with
+    /// `param`, keeping its `query`.
+    fn location<'a>(&self, param: impl Fn(&str) -> Option<&'a str>, query: Option<&str>) -> String {
+        let mut location = String::new();
+        for piece in &self.pieces {
+            match piece {
+                Piece::Static(text) => location.push_str(text),
+                Piece::Param(name) => location.push_str(param(name).unwrap_or_default()),
+            }
+        }
+
+        // A path target starting with a capture, like `/{*path}`, could
+        // otherwise become `//evil.example` — a different host.
+        if location.starts_with('/') {
+            let rest = location.trim_start_matches(['/', '\\']);
+            if rest.len() + 1 < location.len() {
+                location = format!("/{rest}");
+            }
+        }
+
+        if let Some(query) = query.filter(|query| !query.is_empty()) {
+            location.push(if location.contains('?') { '&' } else { '?' });
+            location.push_str(query);
+        }
+        location
+    }
+
+    /// The redirect for a request whose parameters are looked up with
+    /// `param`.
+    pub(crate) fn response<'a>(
+        &self,
+        param: impl Fn(&str) -> Option<&'a str>,
+        query: Option<&str>,
+    ) -> Response {
+        // Captured values come from the request URI, so they're always
+        // valid header characters.
+        HeaderValue::try_from(self.location(param, query)).map_or_else(
+            |_| StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            |location| (self.status, [(header::LOCATION, location)]).into_response(),
+        )
+    }
+}
+
+/// Whether a target starting with `text` points somewhere fixed: a path, or
+/// an absolute URL whose host ends before the first capture.
+fn fixes_origin(text: &str) -> bool {
+    if text.starts_with('/') {
+        return !text.starts_with("//") && !text.starts_with("/\\");
+    }
+    text.split_once("://").is_some_and(|(scheme, rest)| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+            && rest.find(['/', '?', '#']).is_some_and(|end| end > 0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(
+        from: &str,
+        to: &str,
+        params: &[(&'static str, &'static str)],
+        query: Option<&str>,
+    ) -> String {
+        let target = RedirectTarget::new(from, to, StatusCode::PERMANENT_REDIRECT);
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+        target.location(param, query)
+    }
+
+    #[test]
+    fn carries_params_and_query() {
+        assert_eq!(
+            location("/u/{id}", "/users/{id}", &[("id", "42")], None),
+            "/users/42"
+        );
+        assert_eq!(
+            location("/u/{id}", "/users/{id}", &[("id", "42")], Some("tab=posts")),
+            "/users/42?tab=posts"
+        );
+        assert_eq!(
+            location("/old", "/new?from=old", &[], Some("page=2")),
+            "/new?from=old&page=2"
+        );
+    }
+
+    #[test]
+    fn inline_params_and_wildcards() {
+        assert_eq!(
+            location(
+                "/files/{*path}/{name}.{ext}",
+                "/docs/{name}/{*path}.{ext}",
+                &[("path", "a/b%20c"), ("name", "readme"), ("ext", "md")],
+                None,
+            ),
+            "/docs/readme/a/b%20c.md"
+        );
+    }
+
+    #[test]
+    fn captures_cannot_change_the_host() {
+        assert_eq!(
+            location("/o/{*path}", "/{*path}", &[("path", "/evil.com/x")], None),
+            "/evil.com/x"
+        );
+        assert_eq!(
+            location("/o/{*path}", "/{*path}", &[("path", "\\evil.com")], None),
+            "/evil.com"
+        );
+        assert_eq!(
+            location("/o/{*path}", "/{*path}", &[("path", "a//b")], None),
+            "/a//b"
+        );
+    }
+
+    #[test]
+    fn absolute_targets() {
+        assert_eq!(
+            location(
+                "/u/{id}",
+                "https://example.com/users/{id}",
+                &[("id", "7")],
+                None
+            ),
+            "https://example.com/users/7"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "redirect target `{path}` must start with `/` or with a scheme and host")]
+    fn target_starting_with_a_capture_panics() {
+        RedirectTarget::new("/o/{*path}", "{path}", StatusCode::FOUND);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "redirect target `https://{host}/x` must start with `/` or with a scheme and host"
+    )]
+    fn target_with_captured_host_panics() {
+        RedirectTarget::new("/o/{host}", "https://{host}/x", StatusCode::FOUND);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "redirect target `/users/{name}` uses `name`, which `/u/{id}` doesn't capture"
+    )]
+    fn missing_param_panics() {
+        RedirectTarget::new("/u/{id}", "/users/{name}", StatusCode::MOVED_PERMANENTLY);
+    }
+
+    #[test]
+    #[should_panic(expected = "redirect status must be 3xx")]
+    fn non_redirect_status_panics() {
+        RedirectTarget::new("/u/{id}", "/users/{id}", StatusCode::OK);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid redirect target `/users/{id`")]
+    fn invalid_target_panics() {
+        RedirectTarget::new("/u/{id}", "/users/{id", StatusCode::FOUND);
+    }
+}
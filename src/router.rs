@@ -11,26 +11,69 @@
 // all endpoints — including raw services — are wrapped via
 // `axum::routing::any_service()` into `MethodRouter`.
 
+mod future;
+mod trace;
+
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::Infallible,
     fmt,
-    future::{Future, ready},
-    pin::Pin,
+    future::ready,
     sync::Arc,
     task::{Context, Poll},
 };
 
 use axum::routing::MethodRouter;
 use axum_core::{extract::Request, response::IntoResponse};
-use http::StatusCode;
+use http::{Method, StatusCode, Uri, header};
 use tower_layer::Layer;
 use tower_service::Service;
 
+pub use self::future::RouterFuture;
+use self::trace::RouteSpan;
+#[cfg(feature = "cors")]
+use crate::cors::{self, CorsPolicy};
 use crate::{
-    extract::matched_path::MatchedPath, extract::path::WayfindUrlParams, strip_prefix, syntax,
+    extract::matched_path::{MatchedAlias, MatchedPath},
+    extract::path::{ParamKey, WayfindUrlParams},
+    extract::raw_path_params::{RawPathParam, RawPathParams},
+    extract::rejection::{InstalledFormatter, RejectionFormatter},
+    inspect::{AllowedMethods, Candidate, ExplainedRoute, Explanation, RouteMatch},
+    methods,
+    metrics::{InstalledRecorder, MetricsRecorder},
+    pattern::TemplatePattern,
+    policy::RoutePolicy,
+    redirect::RedirectTarget,
+    strip_prefix, syntax,
 };
 
+/// Generate a `Router::typed_*` method registering a handler for one HTTP
+/// method at the template of its first argument.
+#[cfg(feature = "typed-routing")]
+macro_rules! typed_method {
+    ($name:ident, $method:ident, $verb:literal) => {
+        #[doc = concat!("Register `handler` for `", $verb, "` requests at the template of its")]
+        /// first argument, a [`WayfindTypedPath`](crate::typed::WayfindTypedPath).
+        ///
+        /// The path is the struct's `PATH`, so the template, the extractor and
+        /// rendered URLs can't drift apart. Other methods can be added at the
+        /// same path with further `typed_*` calls or [`route`](Self::route).
+        ///
+        /// # Panics
+        ///
+        /// Panics under the same conditions as [`route`](Self::route).
+        #[must_use]
+        pub fn $name<H, T, P>(self, handler: H) -> Self
+        where
+            H: axum::handler::Handler<T, S>,
+            T: crate::typed::SecondElementIs<P> + 'static,
+            P: crate::typed::WayfindTypedPath,
+        {
+            self.route(P::PATH, axum::routing::$method(handler))
+        }
+    };
+}
+
 // ==============================================================================
 // RouteId
 // ==============================================================================
@@ -42,8 +85,103 @@ use crate::{
 /// starting from 0, with no gaps — so `RouteId(n).0 < routes.len()` holds
 /// for every live ID. Both [`Router::nest`] and [`Router::merge`] depend
 /// on this identity when iterating `routes` by index.
+///
+/// IDs are local to a router: nesting or merging re-registers routes and
+/// assigns new IDs. The [`Display`](fmt::Display) form (`#3`) is meant for
+/// diagnostics such as [`Router::explain`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct RouteId(usize);
+pub struct RouteId(usize);
+
+impl fmt::Display for RouteId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+// ==============================================================================
+// Templates
+// ==============================================================================
+
+/// Identifies a template in the wayfind tree: the index of its entry in
+/// `Router::templates`. A route has one template, or several with
+/// [`Router::route_aliases`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TemplateId(usize);
+
+/// A template registered in the wayfind tree, indexed by `TemplateId`.
+#[derive(Clone, Debug)]
+struct Template {
+    /// The route the template dispatches to.
+    route_id: RouteId,
+    /// The Axum-syntax template.
+    path: Arc<str>,
+    /// Parameter names, interned so dispatch can share them instead of
+    /// allocating a key per request.
+    param_keys: Arc<[ParamKey]>,
+    /// Whether this is an alias rather than the route's canonical template.
+    alias: bool,
+}
+
+/// The templates of each route of `routes`, by `RouteId`, canonical first.
+fn paths_by_route(templates: Vec<Template>, routes: usize) -> Vec<Vec<Arc<str>>> {
+    let mut paths = vec![Vec::new(); routes];
+    for template in templates {
+        paths[template.route_id.0].push(template.path);
+    }
+    paths
+}
+
+// ==============================================================================
+// RouteMeta
+// ==============================================================================
+
+/// What the router knows about a route besides its endpoint, indexed by
+/// `RouteId`.
+#[derive(Clone, Debug)]
+struct RouteMeta {
+    kind: RouteKind,
+    /// The methods the route's `MethodRouter` accepts, recorded when it's
+    /// registered so dispatch doesn't have to work them out per request.
+    methods: AllowedMethods,
+    /// Whether the router the route was registered on decodes its
+    /// parameters lossily, kept when the route is nested or merged.
+    lossy_utf8_path_params: bool,
+    /// Whether the router the route was registered on answers `OPTIONS`
+    /// for it, kept when the route is nested or merged.
+    auto_options: bool,
+    /// The formatter of the router the route was registered on, kept when
+    /// the route is nested or merged.
+    rejection_formatter: Option<InstalledFormatter>,
+    /// The recorder of the router the route was registered on, kept when
+    /// the route is nested or merged.
+    metrics_recorder: Option<InstalledRecorder>,
+    /// The route's own CORS policy, overriding the router's.
+    #[cfg(feature = "cors")]
+    cors: Option<Arc<CorsPolicy>>,
+}
+
+/// Give the routes of a router being nested or merged that have no CORS
+/// policy of their own that router's default, which would otherwise be lost.
+#[cfg(feature = "cors")]
+fn inherit_cors(mut route_meta: Vec<RouteMeta>, cors: Option<&Arc<CorsPolicy>>) -> Vec<RouteMeta> {
+    for meta in &mut route_meta {
+        if meta.cors.is_none() {
+            meta.cors = cors.cloned();
+        }
+    }
+    route_meta
+}
+
+/// What a registered route stands for, as reported by tracing and metrics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum RouteKind {
+    /// A route or nested service the user registered.
+    #[default]
+    Route,
+    /// A nested router's fallback, registered under its prefix by
+    /// [`Router::nest`].
+    NestedFallback,
+}
 
 // ==============================================================================
 // Fallback
@@ -70,6 +208,21 @@ where
     }
 }
 
+// ==============================================================================
+// Matching layers
+// ==============================================================================
+
+/// Applies a layer recorded by [`Router::layer_matching`] to a route.
+type ApplyLayer<S> = Arc<dyn Fn(MethodRouter<S>) -> MethodRouter<S> + Send + Sync>;
+
+/// A layer recorded by [`Router::layer_matching`], applied to every route
+/// whose template matches `pattern` — including routes registered later.
+#[derive(Clone)]
+struct MatchingLayer<S> {
+    pattern: TemplatePattern,
+    apply: ApplyLayer<S>,
+}
+
 // ==============================================================================
 // Nesting constants and helpers
 // ==============================================================================
@@ -119,26 +272,85 @@ fn validate_nest_path(path: &str) {
 /// ```
 #[derive(Clone)]
 pub struct Router<S = ()> {
-    /// wayfind path tree: maps translated templates to `RouteId`.
-    wayfind: wayfind::Router<RouteId>,
+    /// wayfind path tree: maps translated templates to `TemplateId`.
+    wayfind: wayfind::Router<TemplateId>,
+    /// Every registered template, aliases included, indexed by
+    /// `TemplateId`.
+    templates: Vec<Template>,
     /// Route endpoints indexed by `RouteId`, all as `MethodRouter`.
     routes: Vec<MethodRouter<S>>,
-    /// `RouteId` → original Axum-syntax template (for `MatchedPath`).
+    /// Per-route metadata, indexed by `RouteId`.
+    route_meta: Vec<RouteMeta>,
+    /// `RouteId` → canonical Axum-syntax template (for `MatchedPath`).
     route_id_to_path: HashMap<RouteId, Arc<str>>,
-    /// Original Axum-syntax template → `RouteId` (for merge detection).
+    /// Axum-syntax template, aliases included → `RouteId` (for merge
+    /// detection).
     path_to_route_id: HashMap<Arc<str>, RouteId>,
     /// What to do when no route matches.
     fallback: Fallback<S>,
+    /// Decode path parameters that aren't valid UTF-8 lossily.
+    lossy_utf8_path_params: bool,
+    /// Answer `OPTIONS` for routes that don't handle it themselves.
+    auto_options: bool,
+    /// Renders extractor rejections, if not plain text.
+    rejection_formatter: Option<InstalledFormatter>,
+    /// Receives per-route request metrics, if installed.
+    metrics_recorder: Option<InstalledRecorder>,
+    /// CORS policy for routes without their own.
+    #[cfg(feature = "cors")]
+    cors: Option<Arc<CorsPolicy>>,
+    /// Layers applied by template pattern, to current and future routes.
+    matching_layers: Vec<MatchingLayer<S>>,
 }
 
 impl<S> fmt::Debug for Router<S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let templates: BTreeMap<usize, &str> = self
+            .route_id_to_path
+            .iter()
+            .map(|(id, path)| (id.0, path.as_ref()))
+            .collect();
+
         f.debug_struct("Router")
-            .field("routes", &self.routes.len())
+            .field("routes", &templates)
+            .field(
+                "custom_fallback",
+                &matches!(self.fallback, Fallback::Handler(_)),
+            )
             .finish_non_exhaustive()
     }
 }
 
+/// Renders the route table followed by the underlying wayfind tree.
+///
+/// Each template is listed with its `RouteId`, its Axum-syntax form and
+/// the translated template wayfind actually stores, so the tree dump can be
+/// read against the templates you registered. Aliases are marked as such.
+impl<S> fmt::Display for Router<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "routes:")?;
+        for template in &self.templates {
+            writeln!(
+                f,
+                "  {} {} -> {}{}",
+                template.route_id,
+                template.path,
+                syntax::axum_to_wayfind(&template.path),
+                if template.alias { " (alias)" } else { "" }
+            )?;
+        }
+
+        let fallback = match self.fallback {
+            Fallback::Default => "default (404)",
+            Fallback::Handler(_) => "custom",
+        };
+        writeln!(f, "fallback: {fallback}")?;
+
+        writeln!(f, "tree:")?;
+        write!(f, "{}", self.wayfind)
+    }
+}
+
 impl Default for Router<()> {
     fn default() -> Self {
         Self::new()
@@ -155,9 +367,18 @@ where
         Self {
             wayfind: wayfind::Router::new(),
             routes: Vec::new(),
+            templates: Vec::new(),
+            route_meta: Vec::new(),
             route_id_to_path: HashMap::new(),
             path_to_route_id: HashMap::new(),
             fallback: Fallback::Default,
+            lossy_utf8_path_params: false,
+            auto_options: false,
+            rejection_formatter: None,
+            metrics_recorder: None,
+            #[cfg(feature = "cors")]
+            cors: None,
+            matching_layers: Vec::new(),
         }
     }
 
@@ -190,41 +411,149 @@ where
         self.route_inner(path, method_router)
     }
 
+    /// Register one `MethodRouter` under several templates, sharing a single
+    /// endpoint and [`RouteId`].
+    ///
+    /// The first template is canonical:
+    /// [`MatchedPath`](crate::extract::MatchedPath) reports it whichever
+    /// template matched, so metrics and traces keyed by it aren't split
+    /// across aliases. [`MatchedAlias`](crate::extract::MatchedAlias) reports
+    /// the template that actually matched. If some of the templates are
+    /// already registered, to a single route, the method routers are merged
+    /// and the others become aliases of that route.
+    ///
+    /// ```rust
+    /// use axum::routing::get;
+    /// use axum_wayfind::{Router, extract::MatchedAlias};
+    ///
+    /// // `/u/7` is handled by the `/users/{id}` route, and `MatchedAlias` is
+    /// // `/u/{id}`.
+    /// let app: Router = Router::new().route_aliases(
+    ///     &["/users/{id}", "/u/{id}"],
+    ///     get(|alias: MatchedAlias| async move { alias.as_str().to_owned() }),
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `paths` is empty, if the already registered templates
+    /// belong to different routes, or if any template would be rejected by
+    /// [`route`](Self::route).
+    #[must_use]
+    #[allow(clippy::panic)] // Intentional: builder panics on invalid routes, matching axum's API.
+    pub fn route_aliases(self, paths: &[&str], method_router: MethodRouter<S>) -> Self {
+        for path in paths {
+            assert!(
+                !path.contains("{__private_") && !path.contains("{*__private_"),
+                "parameter names starting with `__private_` are reserved: `{path}`"
+            );
+        }
+
+        self.register(paths, method_router)
+    }
+
     /// Internal route registration — no `__private_` name check.
     ///
     /// Used by `nest()` and `nest_service()` which legitimately register
     /// routes containing `__private_`-prefixed wildcard parameters.
     #[must_use]
+    fn route_inner(self, path: &str, method_router: MethodRouter<S>) -> Self {
+        self.register(&[path], method_router)
+    }
+
+    /// Register `method_router` under `paths`, the first being canonical,
+    /// without the `__private_` name check.
+    ///
+    /// Also used by `nest()` and `merge()` to re-register another router's
+    /// routes, which may include those names, with their aliases.
+    #[must_use]
     #[allow(clippy::panic)] // Intentional: builder panics on invalid routes, matching axum's API.
-    fn route_inner(mut self, path: &str, method_router: MethodRouter<S>) -> Self {
-        assert!(
-            path.starts_with('/'),
-            "path must start with `/`, got `{path}`"
-        );
+    fn register(mut self, paths: &[&str], method_router: MethodRouter<S>) -> Self {
+        let Some(&canonical) = paths.first() else {
+            panic!("a route needs at least one path");
+        };
+        for path in paths {
+            assert!(
+                path.starts_with('/'),
+                "path must start with `/`, got `{path}`"
+            );
+        }
 
-        let path_arc: Arc<str> = Arc::from(path);
+        let method_router = self.apply_matching_layers(paths, method_router);
 
-        // If this path already exists, merge the method routers.
-        if let Some(&existing_id) = self.path_to_route_id.get(&path_arc) {
-            let existing = std::mem::take(&mut self.routes[existing_id.0]);
-            self.routes[existing_id.0] = existing.merge(method_router);
-            return self;
+        // If some paths already exist, merge the method routers, and the
+        // remaining paths join that route.
+        let mut existing: Vec<RouteId> = paths
+            .iter()
+            .filter_map(|path| self.path_to_route_id.get(*path).copied())
+            .collect();
+        existing.dedup();
+        let route_id = match existing[..] {
+            [] => {
+                let route_id = RouteId(self.routes.len());
+                self.route_meta.push(RouteMeta {
+                    kind: RouteKind::default(),
+                    methods: methods::allowed_methods(&method_router),
+                    lossy_utf8_path_params: false,
+                    auto_options: false,
+                    rejection_formatter: None,
+                    metrics_recorder: None,
+                    #[cfg(feature = "cors")]
+                    cors: None,
+                });
+                self.routes.push(method_router);
+                self.route_id_to_path.insert(route_id, Arc::from(canonical));
+                route_id
+            }
+            [existing_id] => {
+                let existing = std::mem::take(&mut self.routes[existing_id.0]);
+                let merged = existing.merge(method_router);
+                self.route_meta[existing_id.0].methods = methods::allowed_methods(&merged);
+                self.routes[existing_id.0] = merged;
+                existing_id
+            }
+            _ => panic!("paths `{paths:?}` are already registered to different routes"),
+        };
+
+        for path in paths {
+            if !self.path_to_route_id.contains_key(*path) {
+                let alias = self.template_for(route_id).as_ref() != *path;
+                self.insert_template(path, route_id, alias);
+            }
         }
 
-        // New route — translate syntax and insert into wayfind.
-        let route_id = RouteId(self.routes.len());
+        self
+    }
+
+    /// Translate `path` and insert it into wayfind as a template of
+    /// `route_id`.
+    #[allow(clippy::panic)] // Intentional: builder panics on invalid routes, matching axum's API.
+    fn insert_template(&mut self, path: &str, route_id: RouteId, alias: bool) {
+        let template_id = TemplateId(self.templates.len());
         let translated = syntax::axum_to_wayfind(path);
 
         self.wayfind
-            .insert(&translated, route_id)
+            .insert(&translated, template_id)
             .unwrap_or_else(|err| panic!("failed to insert route `{path}`: {err}"));
 
-        self.routes.push(method_router);
-        self.route_id_to_path
-            .insert(route_id, Arc::clone(&path_arc));
-        self.path_to_route_id.insert(path_arc, route_id);
+        let param_keys = syntax::parse(path)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|part| match part {
+                syntax::Part::Param(name) => Some(ParamKey::new(name, false)),
+                syntax::Part::Wildcard(name) => Some(ParamKey::new(name, true)),
+                syntax::Part::Static(_) => None,
+            })
+            .collect();
 
-        self
+        let path: Arc<str> = Arc::from(path);
+        self.templates.push(Template {
+            route_id,
+            path: Arc::clone(&path),
+            param_keys,
+            alias,
+        });
+        self.path_to_route_id.insert(path, route_id);
     }
 
     /// Register an arbitrary tower `Service` at the given path.
@@ -246,6 +575,62 @@ where
         self.route(path, axum::routing::any_service(service))
     }
 
+    /// Redirect every request matching `from` to `to` with `status`, for
+    /// endpoints that have moved.
+    ///
+    /// Parameters and wildcards captured by `from` fill the ones with the
+    /// same name in `to`, exactly as they appeared in the request URI, and
+    /// the query string is kept. `to` may be a path or an absolute URL; it
+    /// is used as-is, so inside a nested router it doesn't get the prefix.
+    ///
+    /// ```rust
+    /// use axum::routing::get;
+    /// use axum_wayfind::Router;
+    /// use http::StatusCode;
+    ///
+    /// // `/u/42?tab=posts` redirects to `/users/42?tab=posts`.
+    /// let app: Router = Router::new()
+    ///     .route("/users/{id}", get(|| async { "user" }))
+    ///     .redirect("/u/{id}", "/users/{id}", StatusCode::PERMANENT_REDIRECT)
+    ///     .redirect("/files/{*path}", "/docs/{*path}", StatusCode::MOVED_PERMANENTLY);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `status` isn't a `3xx` status, if `to` is invalid or uses a
+    /// parameter `from` doesn't capture, or under the same conditions as
+    /// [`route`](Self::route).
+    #[must_use]
+    pub fn redirect(self, from: &str, to: &str, status: StatusCode) -> Self {
+        let target = Arc::new(RedirectTarget::new(from, to, status));
+        let handler = move |params: Option<RawPathParams>, uri: Uri| {
+            let param = |name: &str| params.as_ref()?.get(name).map(RawPathParam::raw);
+            ready(target.response(param, uri.query()))
+        };
+        self.route(from, axum::routing::any(handler))
+    }
+
+    // =========================================================================
+    // Typed routing
+    // =========================================================================
+
+    #[cfg(feature = "typed-routing")]
+    typed_method!(typed_get, get, "GET");
+    #[cfg(feature = "typed-routing")]
+    typed_method!(typed_post, post, "POST");
+    #[cfg(feature = "typed-routing")]
+    typed_method!(typed_put, put, "PUT");
+    #[cfg(feature = "typed-routing")]
+    typed_method!(typed_patch, patch, "PATCH");
+    #[cfg(feature = "typed-routing")]
+    typed_method!(typed_delete, delete, "DELETE");
+    #[cfg(feature = "typed-routing")]
+    typed_method!(typed_head, head, "HEAD");
+    #[cfg(feature = "typed-routing")]
+    typed_method!(typed_options, options, "OPTIONS");
+    #[cfg(feature = "typed-routing")]
+    typed_method!(typed_trace, trace, "TRACE");
+
     // =========================================================================
     // Nesting
     // =========================================================================
@@ -265,45 +650,52 @@ where
     /// Panics if `path` is empty, `"/"`, doesn't start with `/`, or
     /// contains wildcards.
     #[must_use]
-    #[allow(clippy::expect_used)] // Invariant: every RouteId has a corresponding path entry.
     #[allow(clippy::panic)] // Intentional: invalid nest paths are programming errors.
-    pub fn nest(mut self, path: &str, router: Self) -> Self {
+    pub fn nest(mut self, path: &str, mut router: Self) -> Self {
         validate_nest_path(path);
 
+        router.settle_route_meta();
         let Self {
+            templates,
             routes,
-            route_id_to_path,
+            route_meta,
             fallback,
+            lossy_utf8_path_params,
+            auto_options,
+            rejection_formatter,
+            metrics_recorder,
+            #[cfg(feature = "cors")]
+            cors,
             ..
         } = router;
 
-        debug_assert_eq!(
-            routes.len(),
-            route_id_to_path.len(),
-            "RouteId index invariant violated: routes and path map have different lengths"
-        );
+        #[cfg(feature = "cors")]
+        let route_meta = inherit_cors(route_meta, cors.as_ref());
 
         let strip = strip_prefix::StripPrefixLayer::new(path);
-
-        // Flatten: prepend the nest prefix to each inner route and
-        // re-register it in the outer router. StripPrefix ensures handlers
-        // see the URI relative to their original mount point.
-        for (idx, method_router) in routes.into_iter().enumerate() {
-            let id = RouteId(idx);
-            let inner_path = route_id_to_path
-                .get(&id)
-                .expect("every route should have a path");
-            let full_path = format!("{path}{inner_path}");
-            let layered = method_router.layer(strip.clone());
-
-            // When the inner route is "/", the full_path becomes e.g. "/api/".
-            // Also register at the bare prefix ("/api") so requests without a
-            // trailing slash still match.
-            if inner_path.as_ref() == "/" {
-                self = self.route(path, layered.clone());
+        let paths_by_route = paths_by_route(templates, routes.len());
+
+        // Flatten: prepend the nest prefix to each inner route's templates
+        // and re-register it, aliases included, in the outer router.
+        // StripPrefix ensures handlers see the URI relative to their
+        // original mount point.
+        for ((method_router, inner_paths), meta) in
+            routes.into_iter().zip(paths_by_route).zip(&route_meta)
+        {
+            let mut full_paths = Vec::with_capacity(inner_paths.len() + 1);
+            for inner_path in &inner_paths {
+                // The inner route "/" is reached at the bare prefix ("/api"),
+                // with "/api/" as an alias so requests with a trailing slash
+                // still match, sharing the one endpoint.
+                if inner_path.as_ref() == "/" {
+                    full_paths.push(path.to_owned());
+                }
+                full_paths.push(format!("{path}{inner_path}"));
             }
+            let full_paths: Vec<&str> = full_paths.iter().map(String::as_str).collect();
 
-            self = self.route(&full_path, layered);
+            self = self.register(&full_paths, method_router.layer(strip.clone()));
+            self.adopt_meta(full_paths[0], meta);
         }
 
         // If the inner router has a custom fallback, register it as a
@@ -312,17 +704,29 @@ where
         if let Fallback::Handler(fallback_mr) = fallback {
             // `strip` is still available here — the loop only cloned it.
             let layered = (*fallback_mr).layer(strip);
+            let fallback_meta = RouteMeta {
+                kind: RouteKind::NestedFallback,
+                methods: AllowedMethods::Any,
+                lossy_utf8_path_params,
+                auto_options,
+                rejection_formatter,
+                metrics_recorder,
+                #[cfg(feature = "cors")]
+                cors: None,
+            };
 
             // Catch-all for sub-paths under the prefix.
             // Uses `route_inner` because the wildcard contains a `__private_`
             // parameter that would be rejected by `route()`.
             let wildcard = format!("{path}/{{*{NEST_TAIL_PARAM}}}");
             self = self.route_inner(&wildcard, layered.clone());
+            self.adopt_meta(&wildcard, &fallback_meta);
 
             // Also handle the exact prefix for requests like GET /api
             // (only if no inner "/" route already occupies this path).
             if !self.path_to_route_id.contains_key(path) {
                 self = self.route(path, layered);
+                self.adopt_meta(path, &fallback_meta);
             }
         }
 
@@ -383,28 +787,30 @@ where
     ///
     /// Panics if the two routers have conflicting routes.
     #[must_use]
-    #[allow(clippy::expect_used)] // Invariant: every RouteId has a corresponding path entry.
-    pub fn merge(mut self, other: Self) -> Self {
+    pub fn merge(mut self, mut other: Self) -> Self {
+        other.settle_route_meta();
         let Self {
+            templates,
             routes,
-            route_id_to_path,
+            route_meta,
             fallback,
+            #[cfg(feature = "cors")]
+            cors,
             ..
         } = other;
 
-        debug_assert_eq!(
-            routes.len(),
-            route_id_to_path.len(),
-            "RouteId index invariant violated: routes and path map have different lengths"
-        );
+        #[cfg(feature = "cors")]
+        let route_meta = inherit_cors(route_meta, cors.as_ref());
 
-        for (old_id, method_router) in routes.into_iter().enumerate() {
-            let old_id = RouteId(old_id);
-            let path = route_id_to_path
-                .get(&old_id)
-                .expect("every route should have a path");
+        let paths_by_route = paths_by_route(templates, routes.len());
 
-            self = self.route(path, method_router);
+        for ((method_router, paths), meta) in
+            routes.into_iter().zip(paths_by_route).zip(&route_meta)
+        {
+            let paths: Vec<&str> = paths.iter().map(AsRef::as_ref).collect();
+
+            self = self.register(&paths, method_router);
+            self.adopt_meta(paths[0], meta);
         }
 
         // Merge fallback: other's non-default fallback takes precedence.
@@ -496,6 +902,347 @@ where
         self
     }
 
+    /// Apply a [`tower::Layer`] to every route whose template matches
+    /// `pattern`, now and in the future.
+    ///
+    /// The pattern is a template, optionally with `*` segments matching any
+    /// one template segment and `**` segments matching any number of them,
+    /// so `/admin/**` selects `/admin` and every template under it.
+    ///
+    /// Unlike [`route_layer`](Self::route_layer), call order doesn't matter:
+    /// the pattern is recorded, and routes registered later, merged in or
+    /// nested into this router get the layer too if their full template
+    /// matches. Layers recorded on a router that is itself merged or nested
+    /// stay with the routes it had at that point.
+    ///
+    /// ```rust
+    /// use axum::routing::get;
+    /// use axum_wayfind::Router;
+    /// use http::{HeaderValue, header};
+    /// use tower_http::set_header::SetResponseHeaderLayer;
+    ///
+    /// let no_store = SetResponseHeaderLayer::overriding(
+    ///     header::CACHE_CONTROL,
+    ///     HeaderValue::from_static("no-store"),
+    /// );
+    ///
+    /// let app: Router = Router::new()
+    ///     .layer_matching("/admin/**", no_store)
+    ///     // Registered after the call, and still layered.
+    ///     .route("/admin/users/{id}", get(|| async { "user" }))
+    ///     .route("/health", get(|| async { "ok" }));
+    /// ```
+    ///
+    /// Recorded patterns are typed by the router's state, so they don't
+    /// apply to routes added after [`with_state`](Self::with_state).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` doesn't start with `/`.
+    #[must_use]
+    pub fn layer_matching<L>(mut self, pattern: &str, layer: L) -> Self
+    where
+        L: Layer<axum::routing::Route> + Clone + Send + Sync + 'static,
+        L::Service: Service<Request> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        let pattern = TemplatePattern::new(pattern);
+        let apply: ApplyLayer<S> =
+            Arc::new(move |method_router| method_router.layer(layer.clone()));
+
+        self.map_matching_routes(&pattern, |method_router| apply(method_router));
+        self.matching_layers.push(MatchingLayer { pattern, apply });
+        self
+    }
+
+    /// Apply the recorded [`layer_matching`](Self::layer_matching) layers
+    /// whose pattern matches one of `paths` to a route being registered
+    /// there.
+    fn apply_matching_layers(
+        &self,
+        paths: &[&str],
+        method_router: MethodRouter<S>,
+    ) -> MethodRouter<S> {
+        self.matching_layers
+            .iter()
+            .filter(|layer| paths.iter().any(|path| layer.pattern.matches(path)))
+            .fold(method_router, |method_router, layer| {
+                (layer.apply)(method_router)
+            })
+    }
+
+    // =========================================================================
+    // Route policies
+    // =========================================================================
+
+    /// Apply `policy` to the routes registered so far whose template matches
+    /// `pattern`.
+    ///
+    /// The pattern is a template, optionally with `*` segments matching any
+    /// one template segment and `**` segments matching any number of them:
+    /// `/reports/{*path}` selects that route alone, `/admin/**` selects
+    /// `/admin` and every template under it. Each selected route gets its own
+    /// limits, shared by its methods.
+    ///
+    /// Like [`route_layer`](Self::route_layer), the policy is baked into the
+    /// selected routes, so it keeps applying after they are nested or
+    /// merged, and routes registered later aren't affected.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use axum::routing::get;
+    /// use axum_wayfind::{Router, policy::RoutePolicy};
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/reports/{*path}", get(|| async { "report" }))
+    ///     .route_policy(
+    ///         "/reports/{*path}",
+    ///         RoutePolicy::new()
+    ///             .timeout(Duration::from_secs(30))
+    ///             .concurrency_limit(4),
+    ///     );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` doesn't start with `/`.
+    #[must_use]
+    pub fn route_policy(mut self, pattern: &str, policy: RoutePolicy) -> Self {
+        let pattern = TemplatePattern::new(pattern);
+        self.map_matching_routes(&pattern, |method_router| policy.apply(method_router));
+        self
+    }
+
+    /// Replace each route whose template matches `pattern` with
+    /// `f(route)`.
+    fn map_matching_routes<F>(&mut self, pattern: &TemplatePattern, mut f: F)
+    where
+        F: FnMut(MethodRouter<S>) -> MethodRouter<S>,
+    {
+        let selected = self.selected_routes(pattern);
+        for (method_router, selected) in self.routes.iter_mut().zip(selected) {
+            if selected {
+                let taken = std::mem::take(method_router);
+                *method_router = f(taken);
+            }
+        }
+    }
+
+    /// Whether each route, by `RouteId`, has a template or alias matching
+    /// `pattern`.
+    fn selected_routes(&self, pattern: &TemplatePattern) -> Vec<bool> {
+        let mut selected = vec![false; self.routes.len()];
+        for template in &self.templates {
+            if pattern.matches(&template.path) {
+                selected[template.route_id.0] = true;
+            }
+        }
+        selected
+    }
+
+    // =========================================================================
+    // Path parameter decoding
+    // =========================================================================
+
+    /// Decode path parameters that aren't valid UTF-8 lossily instead of
+    /// rejecting them.
+    ///
+    /// By default such a parameter has no decoded string form: `Path<String>`
+    /// rejects it with `InvalidUtf8InPathParam`, while byte targets and
+    /// [`RawPathParams`](crate::extract::RawPathParams) still see the exact
+    /// bytes. With this mode enabled, invalid sequences are replaced by
+    /// U+FFFD so string extractors succeed; handlers that care can compare
+    /// against [`RawPathParam::decoded_bytes`](crate::extract::raw_path_params::RawPathParam::decoded_bytes).
+    ///
+    /// The mode applies to every route this router dispatches, including
+    /// routes merged or nested into it. When this router is nested or
+    /// merged, its routes keep it, without it spreading to the other
+    /// router's routes.
+    #[must_use]
+    pub const fn lossy_utf8_path_params(mut self) -> Self {
+        self.lossy_utf8_path_params = true;
+        self
+    }
+
+    // =========================================================================
+    // Automatic OPTIONS
+    // =========================================================================
+
+    /// Answer `OPTIONS` requests on behalf of routes that don't handle it.
+    ///
+    /// An `OPTIONS` request to a matched route without its own `OPTIONS`
+    /// handler gets `204 No Content` with an `Allow` header listing the
+    /// route's methods — all of them, however the route was assembled from
+    /// `route`, `merge` and `nest` calls. `OPTIONS *` gets the methods of the
+    /// whole router. Routes that accept every method, such as services and
+    /// nested fallbacks, still handle `OPTIONS` themselves, as do unmatched
+    /// paths.
+    ///
+    /// The mode applies to every route this router dispatches, including
+    /// routes merged or nested into it. Nested or merged into another
+    /// router, this router's routes keep the mode but the other router's
+    /// don't gain it, and `OPTIONS *` is up to the other router.
+    ///
+    /// ```rust
+    /// use axum::routing::{get, post};
+    /// use axum_wayfind::Router;
+    ///
+    /// // `OPTIONS /users` answers 204 with `Allow: GET,HEAD,POST,OPTIONS`.
+    /// let app: Router = Router::new()
+    ///     .route("/users", get(|| async {}).post(|| async {}))
+    ///     .auto_options();
+    /// ```
+    #[must_use]
+    pub const fn auto_options(mut self) -> Self {
+        self.auto_options = true;
+        self
+    }
+
+    // =========================================================================
+    // CORS
+    // =========================================================================
+
+    /// Answer CORS preflights and add CORS headers to responses according to
+    /// `policy`, for every route without a policy of its own.
+    ///
+    /// A preflight to a matched route is answered with `204 No Content`
+    /// before it reaches the route, allowing the methods the route accepts —
+    /// however it was assembled from `route`, `merge` and `nest` calls — so
+    /// there is no method list to keep in sync. Responses to actual
+    /// cross-origin requests get the `Access-Control-Allow-Origin` and
+    /// related headers. Unmatched paths and fallbacks are left alone.
+    ///
+    /// The policy applies to routes registered later too. When this router
+    /// is nested or merged, its routes keep it.
+    ///
+    /// ```rust
+    /// use axum::routing::get;
+    /// use axum_wayfind::{Router, cors::CorsPolicy};
+    /// use http::HeaderValue;
+    ///
+    /// // A preflight for `PUT /users/1` is refused: the route only has GET
+    /// // and HEAD.
+    /// let app: Router = Router::new()
+    ///     .route("/users/{id}", get(|| async { "user" }))
+    ///     .cors(CorsPolicy::new().allow_origin(HeaderValue::from_static("https://app.example.com")));
+    /// ```
+    #[cfg(feature = "cors")]
+    #[must_use]
+    pub fn cors(mut self, policy: CorsPolicy) -> Self {
+        self.cors = Some(Arc::new(policy));
+        self
+    }
+
+    /// Use `policy` instead of the router's [`cors`](Self::cors) policy for
+    /// the routes registered so far whose template matches `pattern`.
+    ///
+    /// The pattern is matched as in [`route_policy`](Self::route_policy),
+    /// and a later call overrides an earlier one for the routes both select.
+    /// The policy stays with the routes when they are nested or merged.
+    ///
+    /// ```rust
+    /// use axum::routing::{get, post};
+    /// use axum_wayfind::{Router, cors::CorsPolicy};
+    /// use http::HeaderValue;
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/public/{*path}", get(|| async { "asset" }))
+    ///     .route("/admin/users", post(|| async {}))
+    ///     .cors(CorsPolicy::new().allow_any_origin())
+    ///     .route_cors(
+    ///         "/admin/**",
+    ///         CorsPolicy::new()
+    ///             .allow_origin(HeaderValue::from_static("https://admin.example.com"))
+    ///             .allow_credentials(),
+    ///     );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` doesn't start with `/`.
+    #[cfg(feature = "cors")]
+    #[must_use]
+    pub fn route_cors(mut self, pattern: &str, policy: CorsPolicy) -> Self {
+        let pattern = TemplatePattern::new(pattern);
+        let policy = Arc::new(policy);
+
+        let selected = self.selected_routes(&pattern);
+        for (meta, selected) in self.route_meta.iter_mut().zip(selected) {
+            if selected {
+                meta.cors = Some(Arc::clone(&policy));
+            }
+        }
+        self
+    }
+
+    // =========================================================================
+    // Rejections
+    // =========================================================================
+
+    /// Render [`Path`](crate::extract::Path) and
+    /// [`MatchedPath`](crate::extract::MatchedPath) rejections with
+    /// `formatter` instead of axum's plain text.
+    ///
+    /// The formatter applies to every request this router dispatches,
+    /// fallbacks included. When routers are merged or nested, the outer
+    /// router's formatter wins; an inner one is only used for the inner
+    /// router's own routes, and only if the outer router has none.
+    ///
+    /// ```rust,no_run
+    /// use axum_wayfind::{Router, extract::{Path, rejection::ProblemJson}};
+    /// use axum::routing::get;
+    ///
+    /// // `/users/abc` is rejected with an `application/problem+json` body.
+    /// let app: Router = Router::new()
+    ///     .route("/users/{id}", get(|Path(id): Path<u32>| async move { id.to_string() }))
+    ///     .rejection_formatter(ProblemJson);
+    /// ```
+    #[must_use]
+    pub fn rejection_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: RejectionFormatter,
+    {
+        self.rejection_formatter = Some(InstalledFormatter::new(formatter));
+        self
+    }
+
+    // =========================================================================
+    // Metrics
+    // =========================================================================
+
+    /// Report every request this router dispatches to `recorder`, labelled
+    /// by the matched template rather than the raw path.
+    ///
+    /// Requests handled by a fallback — this router's, or a nested router's
+    /// — are labelled [`UNMATCHED`](crate::metrics::UNMATCHED), so unknown
+    /// paths can't create new series. Latency runs from dispatch until the
+    /// response is ready; requests dropped before then, say because the
+    /// client disconnected, are recorded as cancelled. As with
+    /// [`rejection_formatter`](Self::rejection_formatter), the outer router's
+    /// recorder wins when routers are merged or nested, and an inner one
+    /// only records the inner router's own routes.
+    ///
+    /// ```rust
+    /// use axum::routing::get;
+    /// use axum_wayfind::{Router, metrics::InMemoryMetrics};
+    ///
+    /// let metrics = InMemoryMetrics::new();
+    /// let app: Router = Router::new()
+    ///     .route("/users/{id}", get(|| async { "user" }))
+    ///     .metrics_recorder(metrics.clone());
+    /// ```
+    #[must_use]
+    pub fn metrics_recorder<R>(mut self, recorder: R) -> Self
+    where
+        R: MetricsRecorder,
+    {
+        self.metrics_recorder = Some(InstalledRecorder::new(recorder));
+        self
+    }
+
     // =========================================================================
     // State
     // =========================================================================
@@ -516,9 +1263,187 @@ where
         Router {
             wayfind: self.wayfind,
             routes,
+            templates: self.templates,
+            route_meta: self.route_meta,
             route_id_to_path: self.route_id_to_path,
             path_to_route_id: self.path_to_route_id,
             fallback,
+            lossy_utf8_path_params: self.lossy_utf8_path_params,
+            auto_options: self.auto_options,
+            rejection_formatter: self.rejection_formatter,
+            metrics_recorder: self.metrics_recorder,
+            #[cfg(feature = "cors")]
+            cors: self.cors,
+            // Typed by `S`, so they can't apply to `Router<()>` routes.
+            matching_layers: Vec::new(),
+        }
+    }
+
+    // =========================================================================
+    // Introspection
+    // =========================================================================
+
+    /// Explain how `path` would be routed, without dispatching a request.
+    ///
+    /// The returned [`Explanation`] names the winning route (if any), its
+    /// captured parameters, and every other route whose template would also
+    /// match `path` on its own — the candidates wayfind's priority rules
+    /// chose between. Use its `Display` impl for a readable report.
+    ///
+    /// Finding the candidates probes each route individually, so this is
+    /// meant for tests and troubleshooting rather than the request path.
+    ///
+    /// ```rust
+    /// use axum_wayfind::Router;
+    /// use axum::routing::get;
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/users/me", get(|| async { "me" }))
+    ///     .route("/users/{id}", get(|| async { "user" }));
+    ///
+    /// let explanation = app.explain("/users/me");
+    /// assert_eq!(explanation.matched().map(|m| m.template()), Some("/users/me"));
+    /// assert_eq!(explanation.candidates().len(), 2);
+    /// ```
+    #[must_use]
+    pub fn explain(&self, path: &str) -> Explanation {
+        let matched = self.wayfind.search(path);
+        let selected = matched.as_ref().map(|matched| *matched.data);
+
+        let matched = matched.map(|matched| {
+            let template = &self.templates[matched.data.0];
+            let params = matched
+                .parameters
+                .iter()
+                .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+                .collect();
+
+            ExplainedRoute::new(
+                template.route_id,
+                Arc::clone(&template.path),
+                syntax::axum_to_wayfind(&template.path),
+                params,
+            )
+        });
+
+        // wayfind doesn't report which nodes it visited, so instead we ask,
+        // template by template, whether it alone would match the path.
+        let candidates = self
+            .templates
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, template)| {
+                let mut probe = wayfind::Router::new();
+                probe
+                    .insert(&syntax::axum_to_wayfind(&template.path), ())
+                    .ok()?;
+                probe.search(path)?;
+                Some(Candidate::new(
+                    template.route_id,
+                    Arc::clone(&template.path),
+                    selected == Some(TemplateId(idx)),
+                ))
+            })
+            .collect();
+
+        Explanation::new(path.to_owned(), matched, candidates)
+    }
+
+    /// Resolve `path` to a route without invoking any handler.
+    ///
+    /// Returns the matched template, the decoded path parameters and the
+    /// methods the route accepts, or `None` if the fallback would handle the
+    /// request. This never panics, so routing tables can be unit-tested
+    /// without building requests:
+    ///
+    /// ```rust
+    /// use axum_wayfind::{Router, inspect::AllowedMethods};
+    /// use axum::routing::get;
+    /// use http::Method;
+    ///
+    /// let app: Router = Router::new().route("/users/{id}", get(|| async {}));
+    ///
+    /// let found = app.at("/users/42").expect("route should match");
+    /// assert_eq!(found.template(), "/users/{id}");
+    /// assert_eq!(found.deserialize::<u32>().ok(), Some(42));
+    /// assert_eq!(
+    ///     found.allowed_methods(),
+    ///     &AllowedMethods::Only(vec![Method::GET, Method::HEAD]),
+    /// );
+    ///
+    /// assert!(app.at("/nope").is_none());
+    /// ```
+    #[must_use]
+    pub fn at(&self, path: &str) -> Option<RouteMatch> {
+        let matched = self.wayfind.search(path)?;
+        let template = &self.templates[matched.data.0];
+        let route_id = template.route_id;
+
+        Some(RouteMatch::new(
+            route_id,
+            Arc::clone(self.template_for(route_id)),
+            WayfindUrlParams::from_match(
+                &matched,
+                None,
+                &template.param_keys,
+                self.lossy_for(route_id),
+            ),
+            self.route_meta[route_id.0].methods.clone(),
+        ))
+    }
+
+    /// Look up the original template for a registered route.
+    #[allow(clippy::expect_used)] // Invariant: every RouteId has a corresponding path entry.
+    fn template_for(&self, route_id: RouteId) -> &Arc<str> {
+        self.route_id_to_path
+            .get(&route_id)
+            .expect("every route should have a path")
+    }
+
+    /// Whether `route_id`'s parameters are decoded lossily: because this
+    /// router does so, or the router the route came from did.
+    fn lossy_for(&self, route_id: RouteId) -> bool {
+        self.lossy_utf8_path_params || self.route_meta[route_id.0].lossy_utf8_path_params
+    }
+
+    /// Record this router's modes on each of its routes, before `nest` or
+    /// `merge` hands them to another router, so they keep applying to these
+    /// routes and only to them.
+    fn settle_route_meta(&mut self) {
+        for meta in &mut self.route_meta {
+            meta.lossy_utf8_path_params |= self.lossy_utf8_path_params;
+            meta.auto_options |= self.auto_options;
+            if self.rejection_formatter.is_some() {
+                meta.rejection_formatter.clone_from(&self.rejection_formatter);
+            }
+            if self.metrics_recorder.is_some() {
+                meta.metrics_recorder.clone_from(&self.metrics_recorder);
+            }
+        }
+    }
+
+    /// Carry another router's metadata for a route over to the route it was
+    /// re-registered as at `path`, by `nest` or `merge`.
+    fn adopt_meta(&mut self, path: &str, meta: &RouteMeta) {
+        let Some(&route_id) = self.path_to_route_id.get(path) else {
+            return;
+        };
+        let target = &mut self.route_meta[route_id.0];
+
+        if meta.kind == RouteKind::NestedFallback {
+            target.kind = RouteKind::NestedFallback;
+        }
+        target.lossy_utf8_path_params |= meta.lossy_utf8_path_params;
+        target.auto_options |= meta.auto_options;
+        if target.rejection_formatter.is_none() {
+            target.rejection_formatter.clone_from(&meta.rejection_formatter);
+        }
+        if target.metrics_recorder.is_none() {
+            target.metrics_recorder.clone_from(&meta.metrics_recorder);
+        }
+        #[cfg(feature = "cors")]
+        if meta.cors.is_some() {
+            target.cors.clone_from(&meta.cors);
         }
     }
 
@@ -547,7 +1472,7 @@ where
 impl Service<Request> for Router<()> {
     type Response = axum::response::Response;
     type Error = Infallible;
-    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+    type Future = RouterFuture;
 
     fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         Poll::Ready(Ok(()))
@@ -555,37 +1480,123 @@ impl Service<Request> for Router<()> {
 
     #[allow(clippy::expect_used)] // Invariant: every RouteId has a corresponding path entry.
     fn call(&mut self, mut req: Request) -> Self::Future {
-        // Search the wayfind tree for a matching route.
-        let path = req.uri().path().to_owned();
-
-        match self.wayfind.search(&path) {
-            Some(matched) => {
-                let route_id = *matched.data;
-
-                // Build the URL parameters from the wayfind match,
-                // percent-decoding each value.
-                let params = WayfindUrlParams::from_match(&matched);
-                req.extensions_mut().insert(params);
-
-                // Insert MatchedPath using the original Axum-syntax template.
-                let template = self
-                    .route_id_to_path
-                    .get(&route_id)
-                    .expect("every route should have a path");
+        // Search the wayfind tree for a matching route. Cloning the `Uri`
+        // only bumps refcounts, and lets the captured values keep pointing
+        // into its path buffer after the request is handed on.
+        let uri = req.uri().clone();
+
+        // `OPTIONS *` asks about the server as a whole, not a route.
+        if self.auto_options && req.method() == Method::OPTIONS && uri.path() == "*" {
+            let span = RouteSpan::fallback(&req);
+            let metrics = self
+                .metrics_recorder
+                .as_ref()
+                .map(|recorder| recorder.start(req.method(), None));
+            let allow = methods::allow_header(&methods::server_allowed_methods(
+                self.route_meta.iter().map(|meta| &meta.methods),
+            ));
+            return RouterFuture::no_content(vec![(header::ALLOW, allow)], span, metrics);
+        }
+
+        if let Some(matched) = self.wayfind.search(uri.path()) {
+            let matched_template = &self.templates[matched.data.0];
+            let route_id = matched_template.route_id;
+
+            // Capture the URL parameters from the wayfind match. They're
+            // percent-decoded lazily, by the extractors that read them.
+            let params = WayfindUrlParams::from_match(
+                &matched,
+                uri.path_and_query(),
+                &matched_template.param_keys,
+                self.lossy_for(route_id),
+            );
+            req.extensions_mut().insert(params);
+
+            // Insert MatchedPath using the route's canonical Axum-syntax
+            // template, and the alias if that's what matched.
+            let template = self
+                .route_id_to_path
+                .get(&route_id)
+                .expect("every route should have a path");
+            req.extensions_mut()
+                .insert(MatchedPath(Arc::clone(template)));
+            if matched_template.alias {
                 req.extensions_mut()
-                    .insert(MatchedPath(Arc::clone(template)));
+                    .insert(MatchedAlias(Arc::clone(&matched_template.path)));
+            }
+
+            // The route is known from here on, so the span and metrics
+            // can carry it. Nested fallbacks count as unmatched.
+            let meta = &self.route_meta[route_id.0];
+            if let Some(formatter) = self
+                .rejection_formatter
+                .as_ref()
+                .or(meta.rejection_formatter.as_ref())
+            {
+                req.extensions_mut().insert(formatter.clone());
+            }
+            let kind = meta.kind;
+            let span = RouteSpan::matched(&req, route_id, template, kind);
+            let metrics = self
+                .metrics_recorder
+                .as_ref()
+                .or(meta.metrics_recorder.as_ref())
+                .map(|recorder| {
+                    let label = (kind == RouteKind::Route).then(|| Arc::clone(template));
+                    recorder.start(req.method(), label)
+                });
+
+            // Nested fallbacks count as unmatched here too.
+            #[cfg(feature = "cors")]
+            let cors_policy = match kind {
+                RouteKind::Route => meta.cors.as_ref().or(self.cors.as_ref()),
+                RouteKind::NestedFallback => None,
+            };
+
+            #[cfg(feature = "cors")]
+            if let Some(policy) = cors_policy
+                && cors::is_preflight(&req)
+            {
+                let headers = policy.preflight(req.headers(), &meta.methods);
+                return RouterFuture::no_content(headers, span, metrics);
+            }
+
+            if (self.auto_options || meta.auto_options)
+                && req.method() == Method::OPTIONS
+                && let AllowedMethods::Only(allowed) = &meta.methods
+                && !allowed.contains(&Method::OPTIONS)
+            {
+                let allow = methods::allow_header(allowed);
+                return RouterFuture::no_content(vec![(header::ALLOW, allow)], span, metrics);
+            }
+
+            let route = &mut self.routes[route_id.0];
+
+            #[cfg(feature = "cors")]
+            if let Some(policy) = cors_policy {
+                let headers = policy.actual(req.headers());
+                let future = span.in_scope(|| route.call(req));
+                return RouterFuture::route(future, span, metrics).with_headers(headers);
+            }
 
-                let mut mr = self.routes[route_id.0].clone();
-                Box::pin(async move { mr.call(req).await })
+            let future = span.in_scope(|| route.call(req));
+            RouterFuture::route(future, span, metrics)
+        } else {
+            // No route matched — invoke the fallback.
+            if let Some(formatter) = &self.rejection_formatter {
+                req.extensions_mut().insert(formatter.clone());
             }
-            None => {
-                // No route matched — invoke the fallback.
-                match &self.fallback {
-                    Fallback::Default => Box::pin(ready(Ok(StatusCode::NOT_FOUND.into_response()))),
-                    Fallback::Handler(mr) => {
-                        let mut mr = mr.clone();
-                        Box::pin(async move { mr.call(req).await })
-                    }
+            let span = RouteSpan::fallback(&req);
+            let metrics = self
+                .metrics_recorder
+                .as_ref()
+                .map(|recorder| recorder.start(req.method(), None));
+
+            match &mut self.fallback {
+                Fallback::Default => RouterFuture::not_found(span, metrics),
+                Fallback::Handler(mr) => {
+                    let future = span.in_scope(|| mr.call(req));
+                    RouterFuture::route(future, span, metrics)
                 }
             }
         }
@@ -0,0 +1,123 @@
+// ==============================================================================
+// RouterFuture — the response future of `Router::call`
+// ==============================================================================
+//
+// A named, unboxed future: either the matched route's (or fallback's)
+// `RouteFuture`, or a response the router answers itself — the default 404
+// or a `204` for an automatic `OPTIONS` or a CORS preflight — built when
+// polled. Headers the router adds, such as CORS ones, are appended to the
+// response. Each poll runs inside the request's `RouteSpan`, and the
+// request's metrics are recorded once the response is ready.
+
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::routing::future::RouteFuture;
+use axum_core::response::{IntoResponse, Response};
+use http::{HeaderName, HeaderValue, StatusCode};
+use pin_project_lite::pin_project;
+
+use super::RouteSpan;
+use crate::metrics::PendingRequest;
+
+pin_project! {
+    /// Response future for [`Router`](crate::Router).
+    pub struct RouterFuture {
+        #[pin]
+        kind: Kind,
+        span: RouteSpan,
+        metrics: Option<PendingRequest>,
+        headers: Vec<(HeaderName, HeaderValue)>,
+    }
+}
+
+pin_project! {
+    #[project = KindProj]
+    enum Kind {
+        Route {
+            #[pin]
+            future: RouteFuture<Infallible>,
+        },
+        NotFound,
+        NoContent,
+    }
+}
+
+impl RouterFuture {
+    pub(super) const fn route(
+        future: RouteFuture<Infallible>,
+        span: RouteSpan,
+        metrics: Option<PendingRequest>,
+    ) -> Self {
+        Self {
+            kind: Kind::Route { future },
+            span,
+            metrics,
+            headers: Vec::new(),
+        }
+    }
+
+    pub(super) const fn not_found(span: RouteSpan, metrics: Option<PendingRequest>) -> Self {
+        Self {
+            kind: Kind::NotFound,
+            span,
+            metrics,
+            headers: Vec::new(),
+        }
+    }
+
+    /// An empty `204 No Content` carrying `headers`.
+    pub(super) const fn no_content(
+        headers: Vec<(HeaderName, HeaderValue)>,
+        span: RouteSpan,
+        metrics: Option<PendingRequest>,
+    ) -> Self {
+        Self {
+            kind: Kind::NoContent,
+            span,
+            metrics,
+            headers,
+        }
+    }
+
+    /// Append `headers` to the response once it's ready.
+    #[cfg(feature = "cors")]
+    pub(super) fn with_headers(mut self, headers: Vec<(HeaderName, HeaderValue)>) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+}
+
+impl Future for RouterFuture {
+    type Output = Result<Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let poll = this.span.in_scope(|| match this.kind.project() {
+            KindProj::Route { future } => future.poll(cx),
+            KindProj::NotFound => Poll::Ready(Ok(StatusCode::NOT_FOUND.into_response())),
+            KindProj::NoContent => Poll::Ready(Ok(StatusCode::NO_CONTENT.into_response())),
+        });
+
+        let Poll::Ready(Ok(mut response)) = poll else {
+            return Poll::Pending;
+        };
+        for (name, value) in this.headers.drain(..) {
+            response.headers_mut().append(name, value);
+        }
+        if let Some(metrics) = this.metrics.take() {
+            metrics.finish(response.status());
+        }
+        Poll::Ready(Ok(response))
+    }
+}
+
+impl std::fmt::Debug for RouterFuture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouterFuture").finish_non_exhaustive()
+    }
+}
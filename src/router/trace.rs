@@ -0,0 +1,137 @@
+// ==============================================================================
+// RouteSpan — the per-request span opened by `Router::call`
+// ==============================================================================
+//
+// With the `tracing` feature, dispatch opens a `route` span as soon as the
+// route is known, named and attributed after OpenTelemetry's HTTP semantic
+// conventions. Spans opened by middleware in front of the router (such as
+// `tower_http::trace`) start before routing and can't know the template;
+// this one nests inside them.
+//
+// Without the feature, `RouteSpan` is a zero-sized no-op, so the dispatch
+// code doesn't need `cfg`s of its own.
+
+use axum_core::extract::Request;
+
+use super::{RouteId, RouteKind};
+
+#[cfg(feature = "tracing")]
+pub(super) use self::enabled::RouteSpan;
+
+#[cfg(not(feature = "tracing"))]
+pub(super) use self::disabled::RouteSpan;
+
+#[cfg(feature = "tracing")]
+mod enabled {
+    use super::{Request, RouteId, RouteKind};
+    use crate::{methods, metrics::OTHER_METHOD};
+
+    /// The span a request is dispatched in.
+    ///
+    /// Fields:
+    ///
+    /// - `otel.name`: `"{method} {template}"`, or just the method for the
+    ///   fallback, with `HTTP` standing in for a non-standard method,
+    /// - `http.request.method`: the method, or `_OTHER` if it isn't a
+    ///   standard one,
+    /// - `http.request.method_original`: a non-standard method as sent,
+    /// - `http.route`: the Axum-syntax template, absent for the fallback,
+    /// - `route.id`: the [`RouteId`], absent for the fallback,
+    /// - `route.handler`: `"route"`, `"nested_fallback"` or `"fallback"`.
+    #[derive(Debug)]
+    pub(in crate::router) struct RouteSpan(tracing::Span);
+
+    impl RouteSpan {
+        pub(in crate::router) fn matched(
+            req: &Request,
+            route_id: RouteId,
+            template: &str,
+            kind: RouteKind,
+        ) -> Self {
+            let method = methods::method_label(req.method());
+            let handler = match kind {
+                RouteKind::Route => "route",
+                RouteKind::NestedFallback => "nested_fallback",
+            };
+
+            let span = tracing::info_span!(
+                "route",
+                otel.name = tracing::field::Empty,
+                http.request.method = method,
+                http.request.method_original = tracing::field::Empty,
+                http.route = template,
+                route.id = route_id.0,
+                route.handler = handler,
+            );
+            span.record(
+                "otel.name",
+                format_args!("{} {template}", span_method(method)),
+            );
+            record_original(&span, req, method);
+            Self(span)
+        }
+
+        pub(in crate::router) fn fallback(req: &Request) -> Self {
+            let method = methods::method_label(req.method());
+
+            let span = tracing::info_span!(
+                "route",
+                otel.name = span_method(method),
+                http.request.method = method,
+                http.request.method_original = tracing::field::Empty,
+                route.handler = "fallback",
+            );
+            record_original(&span, req, method);
+            Self(span)
+        }
+
+        /// Run `f` with the span entered.
+        pub(in crate::router) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+            self.0.in_scope(f)
+        }
+    }
+
+    /// The method as it appears in the span name: `HTTP` for a
+    /// non-standard one.
+    fn span_method(label: &'static str) -> &'static str {
+        if label == OTHER_METHOD { "HTTP" } else { label }
+    }
+
+    /// Keep a non-standard method as sent, since `http.request.method` only
+    /// says `_OTHER`.
+    fn record_original(span: &tracing::Span, req: &Request, label: &str) {
+        if label == OTHER_METHOD {
+            span.record("http.request.method_original", req.method().as_str());
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod disabled {
+    use super::{Request, RouteId, RouteKind};
+
+    /// Stand-in for the `tracing` feature's span: records nothing.
+    #[derive(Debug)]
+    pub(in crate::router) struct RouteSpan;
+
+    impl RouteSpan {
+        pub(in crate::router) const fn matched(
+            _req: &Request,
+            _route_id: RouteId,
+            _template: &str,
+            _kind: RouteKind,
+        ) -> Self {
+            Self
+        }
+
+        pub(in crate::router) const fn fallback(_req: &Request) -> Self {
+            Self
+        }
+
+        /// Run `f`.
+        #[allow(clippy::unused_self)] // Mirrors the `tracing` feature's signature.
+        pub(in crate::router) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+            f()
+        }
+    }
+}
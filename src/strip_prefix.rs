@@ -72,10 +72,17 @@ where
     }
 
     fn call(&mut self, mut req: Request<B>) -> Self::Future {
-        // If the prefix doesn't match (e.g. exact-prefix route with no
-        // trailing path segments), forward the original URI unchanged.
-        // This is intentional and matches axum's StripPrefix behavior.
-        if let Some(new_uri) = strip_prefix(req.uri(), &self.prefix) {
+        // The router only dispatches here for paths under the prefix, so a
+        // mismatch is a routing bug. Release builds forward the original
+        // URI unchanged, as axum's StripPrefix does.
+        let stripped = strip_prefix(req.uri(), &self.prefix);
+        debug_assert!(
+            stripped.is_some(),
+            "nested route `{}` received `{}`, which isn't under its prefix",
+            self.prefix,
+            req.uri()
+        );
+        if let Some(new_uri) = stripped {
             *req.uri_mut() = new_uri;
         }
         self.inner.call(req)
@@ -93,9 +100,12 @@ where
 ///
 /// Example: prefix = "/api", path = "/api/users/42"
 ///          matched length = 4 ("/api"), remainder = "/users/42"
+///
+/// Returns `None` if the prefix doesn't match, or if the remainder somehow
+/// doesn't form a valid URI; the caller then forwards the original URI.
 #[allow(clippy::option_if_let_else)] // Control flow is clearer with match here.
-#[allow(clippy::expect_used)] // Invariant: stripping a valid prefix always yields a valid URI.
-fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
+#[allow(clippy::redundant_pub_crate)] // Explicit crate visibility on private-module item.
+pub(crate) fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
     let path_and_query = uri.path_and_query()?;
 
     let mut matched_len = Some(0_usize);
@@ -123,19 +133,17 @@ fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
         }
     }
 
-    // The prefix always matches at a `/` boundary, so `split_at` won't panic.
-    let after_prefix = uri.path().split_at(matched_len?).1;
+    // The prefix matches at a `/` boundary, so this is in range; `get`
+    // keeps a broken invariant from panicking on untrusted input.
+    let after_prefix = uri.path().get(matched_len?..)?;
 
     let new_path_and_query = match (after_prefix.starts_with('/'), path_and_query.query()) {
-        (true, None) => after_prefix.parse().expect("valid path"),
-        (true, Some(query)) => format!("{after_prefix}?{query}")
-            .parse()
-            .expect("valid path+query"),
-        (false, None) => format!("/{after_prefix}").parse().expect("valid path"),
-        (false, Some(query)) => format!("/{after_prefix}?{query}")
-            .parse()
-            .expect("valid path+query"),
-    };
+        (true, None) => after_prefix.parse(),
+        (true, Some(query)) => format!("{after_prefix}?{query}").parse(),
+        (false, None) => format!("/{after_prefix}").parse(),
+        (false, Some(query)) => format!("/{after_prefix}?{query}").parse(),
+    }
+    .ok()?;
 
     // Build the new URI from parts without cloning the entire original URI.
     // For origin-form requests (the common case), scheme and authority are
@@ -145,7 +153,7 @@ fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
     parts.authority = uri.authority().cloned();
     parts.path_and_query = Some(new_path_and_query);
 
-    Some(Uri::from_parts(parts).expect("valid URI"))
+    Uri::from_parts(parts).ok()
 }
 
 // ==============================================================================
@@ -195,6 +203,21 @@ mod tests {
     #![allow(clippy::expect_used)] // Tests panic on failure by design.
 
     use super::*;
+    use tower_layer::Layer as _;
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "nested route `/api` received `/other/users`")]
+    fn mismatched_prefix_is_a_routing_bug() {
+        let mut svc = StripPrefixLayer::new("/api").layer(tower::service_fn(
+            |req: Request<()>| async move { Ok::<_, std::convert::Infallible>(req.uri().clone()) },
+        ));
+        let req = Request::builder()
+            .uri("/other/users")
+            .body(())
+            .expect("valid request");
+        drop(svc.call(req));
+    }
 
     fn strip(uri: &str, prefix: &str) -> Option<String> {
         let uri: Uri = uri.parse().expect("valid URI");
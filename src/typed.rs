@@ -0,0 +1,176 @@
+// ==============================================================================
+// Typed Routing
+// ==============================================================================
+//
+// The equivalent of axum-extra's `TypedPath` for this router. A struct
+// deriving `WayfindTypedPath` carries its template, renders its own URL and
+// extracts itself through our `Path`, and `Router::typed_get` & co. register
+// a handler at the template of its first argument.
+
+use std::fmt;
+
+use http::Uri;
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+
+pub use axum_wayfind_macros::WayfindTypedPath;
+
+/// A type-safe path: a struct whose fields are the parameters of a route
+/// template.
+///
+/// Derive it, together with `Deserialize`, and name the template with
+/// `#[typed_path]`. Any template the router accepts works, including
+/// wayfind-only patterns such as inline parameters and mid-path wildcards.
+/// The template is checked against the struct's fields at compile time.
+///
+/// ```rust
+/// use axum_wayfind::typed::WayfindTypedPath;
+/// use serde::Deserialize;
+///
+/// #[derive(WayfindTypedPath, Deserialize)]
+/// #[typed_path("/files/{name}.{ext}")]
+/// struct FilePath {
+///     name: String,
+///     ext: String,
+/// }
+///
+/// #[derive(WayfindTypedPath, Deserialize)]
+/// #[typed_path("/{*repo}/tree/{branch}")]
+/// struct TreePath {
+///     repo: String,
+///     branch: String,
+/// }
+///
+/// let file = FilePath { name: "my report".to_owned(), ext: "pdf".to_owned() };
+/// assert_eq!(file.to_string(), "/files/my%20report.pdf");
+///
+/// // Wildcard values keep their `/` separators.
+/// let tree = TreePath { repo: "org/repo".to_owned(), branch: "main".to_owned() };
+/// assert_eq!(tree.to_uri(), "/org/repo/tree/main");
+/// ```
+///
+/// Handlers take the struct as their first argument, and
+/// [`Router::typed_get`](crate::Router::typed_get) registers them at its
+/// template:
+///
+/// ```rust,no_run
+/// # use axum_wayfind::typed::WayfindTypedPath;
+/// # use serde::Deserialize;
+/// # #[derive(WayfindTypedPath, Deserialize)]
+/// # #[typed_path("/files/{name}.{ext}")]
+/// # struct FilePath { name: String, ext: String }
+/// use axum_wayfind::Router;
+///
+/// async fn download(FilePath { name, ext }: FilePath) -> String {
+///     format!("{name} as {ext}")
+/// }
+///
+/// let app: Router = Router::new().typed_get(download);
+/// ```
+///
+/// Rendering requires every field to implement [`Display`](fmt::Display);
+/// values are percent-encoded so they round-trip through extraction. The
+/// template's own text is copied as-is, so it must already be valid in a
+/// URI path:
+///
+/// ```rust,compile_fail
+/// # use axum_wayfind::typed::WayfindTypedPath;
+/// #[derive(WayfindTypedPath, serde::Deserialize)]
+/// #[typed_path("/my files/{name}")]
+/// struct FilePath {
+///     name: String,
+/// }
+/// ```
+pub trait WayfindTypedPath: fmt::Display {
+    /// The Axum-syntax route template.
+    const PATH: &'static str;
+
+    /// Render this path as a URI.
+    #[must_use]
+    #[allow(clippy::expect_used)] // Invariant: values are percent-encoded and the derive checks the template's text.
+    fn to_uri(&self) -> Uri {
+        self.to_string()
+            .parse()
+            .expect("a rendered typed path should be a valid URI")
+    }
+}
+
+// ==============================================================================
+// Handler argument matching
+// ==============================================================================
+
+/// Implemented for a handler's argument tuple whose first extractor is `P`.
+///
+/// axum's `Handler<T, S>` impls use `T = (M, T1, T2, …)`, where `M` is an
+/// internal marker, so "the first argument is `P`" is "the second element
+/// is `P`". This lets [`Router::typed_get`](crate::Router::typed_get) find
+/// the template from the handler alone.
+pub trait SecondElementIs<P>: sealed::Sealed {}
+
+mod sealed {
+    #[allow(unreachable_pub)] // Sealed: nameable but not implementable outside the crate.
+    pub trait Sealed {}
+}
+
+macro_rules! impl_second_element_is {
+    ( $($ty:ident),* $(,)? ) => {
+        impl<M, P, $($ty,)*> SecondElementIs<P> for (M, P, $($ty,)*) where P: WayfindTypedPath {}
+
+        impl<M, P, $($ty,)*> sealed::Sealed for (M, P, $($ty,)*) where P: WayfindTypedPath {}
+    };
+}
+
+impl_second_element_is!();
+impl_second_element_is!(T1);
+impl_second_element_is!(T1, T2);
+impl_second_element_is!(T1, T2, T3);
+impl_second_element_is!(T1, T2, T3, T4);
+impl_second_element_is!(T1, T2, T3, T4, T5);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6, T7);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_second_element_is!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_second_element_is!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15
+);
+
+// ==============================================================================
+// Rendering helpers used by the derive
+// ==============================================================================
+
+/// Characters escaped in a rendered path segment: everything that would
+/// change how the path is parsed or decoded.
+const SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Write a parameter value, percent-encoded as a single segment.
+#[doc(hidden)]
+pub fn write_segment(f: &mut fmt::Formatter<'_>, value: &dyn fmt::Display) -> fmt::Result {
+    write!(f, "{}", utf8_percent_encode(&value.to_string(), SEGMENT))
+}
+
+/// Write a wildcard value, percent-encoding each `/`-separated segment.
+#[doc(hidden)]
+pub fn write_wildcard(f: &mut fmt::Formatter<'_>, value: &dyn fmt::Display) -> fmt::Result {
+    for (idx, segment) in value.to_string().split('/').enumerate() {
+        if idx > 0 {
+            f.write_str("/")?;
+        }
+        write!(f, "{}", utf8_percent_encode(segment, SEGMENT))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,297 @@
+#[cfg(test)]
+#[allow(clippy::expect_used)] // Tests panic on failure by design.
+mod tests {
+    // ==============================================================================
+    // Differential testing against `axum::Router`
+    // ==============================================================================
+    //
+    // Generates random route sets both matchit and wayfind accept, loads them
+    // into `axum::Router` and `axum_wayfind::Router`, and checks that random
+    // requests see the same status, `MatchedPath`, `Path` values and URI —
+    // directly and under `nest`.
+    //
+    // Counterexamples proptest finds are shrunk and saved to
+    // `tests/differential.proptest-regressions`, which is checked in and
+    // replayed first on every run. Each one should also get a named test in
+    // the Regressions section below.
+
+    use std::collections::BTreeMap;
+
+    use axum::{body::Body, routing::get};
+    use http::{Request, StatusCode, Uri};
+    use proptest::prelude::*;
+    use tower::ServiceExt;
+
+    // ==============================================================================
+    // Handlers
+    // ==============================================================================
+
+    /// What `axum::Router` showed the handler.
+    async fn axum_echo(
+        matched: axum::extract::MatchedPath,
+        axum::extract::Path(params): axum::extract::Path<BTreeMap<String, String>>,
+        uri: Uri,
+    ) -> String {
+        format!("{}|{params:?}|{uri}", matched.as_str())
+    }
+
+    /// What `axum_wayfind::Router` showed the handler.
+    async fn wayfind_echo(
+        matched: axum_wayfind::extract::MatchedPath,
+        axum_wayfind::extract::Path(params): axum_wayfind::extract::Path<BTreeMap<String, String>>,
+        uri: Uri,
+    ) -> String {
+        format!("{}|{params:?}|{uri}", matched.as_str())
+    }
+
+    fn axum_router(routes: &[String]) -> axum::Router {
+        routes.iter().fold(axum::Router::new(), |router, route| {
+            router.route(route, get(axum_echo))
+        })
+    }
+
+    fn wayfind_router(routes: &[String]) -> axum_wayfind::Router {
+        routes
+            .iter()
+            .fold(axum_wayfind::Router::new(), |router, route| {
+                router.route(route, get(wayfind_echo))
+            })
+    }
+
+    /// Status and body of a GET to `uri`.
+    async fn respond<S>(service: S, uri: &str) -> (StatusCode, String)
+    where
+        S: tower::Service<
+                Request<Body>,
+                Response = axum::response::Response,
+                Error = std::convert::Infallible,
+            >,
+    {
+        let request = Request::builder()
+            .uri(uri)
+            .body(Body::empty())
+            .expect("valid request");
+        let response = service.oneshot(request).await.expect("infallible");
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// Send every request to both routers, directly and nested under
+    /// `/nest`, and check they agree.
+    fn assert_same(routes: &[String], requests: &[String]) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("tokio runtime");
+
+        runtime.block_on(async {
+            let axum = axum_router(routes);
+            let wayfind = wayfind_router(routes);
+            // A nested `/` route also matches `/nest/` in axum_wayfind but not
+            // in axum 0.8; that difference is documented, so it's left out.
+            let nestable: Vec<String> = routes.iter().filter(|r| *r != "/").cloned().collect();
+            let axum_nested = axum::Router::new().nest("/nest", axum_router(&nestable));
+            let wayfind_nested =
+                axum_wayfind::Router::new().nest("/nest", wayfind_router(&nestable));
+
+            for request in requests {
+                assert_eq!(
+                    respond(wayfind.clone(), request).await,
+                    respond(axum.clone(), request).await,
+                    "routes {routes:?}, request {request}"
+                );
+
+                let nested = format!("/nest{request}");
+                assert_eq!(
+                    respond(wayfind_nested.clone(), &nested).await,
+                    respond(axum_nested.clone(), &nested).await,
+                    "nested routes {nestable:?}, request {nested}"
+                );
+            }
+        });
+    }
+
+    // ==============================================================================
+    // Generators
+    // ==============================================================================
+
+    /// A template segment. Parameters are named by depth so that two routes
+    /// never give the same position different names, which matchit rejects.
+    #[derive(Clone, Debug)]
+    enum Segment {
+        Static(&'static str),
+        Param,
+    }
+
+    const STATICS: &[&str] = &["a", "b", "users", "v1", "x.json"];
+
+    fn segment() -> impl Strategy<Value = Segment> {
+        prop_oneof![
+            3 => proptest::sample::select(STATICS).prop_map(Segment::Static),
+            2 => Just(Segment::Param),
+        ]
+    }
+
+    /// A template: up to four segments, optionally ending in a wildcard.
+    fn template() -> impl Strategy<Value = String> {
+        (proptest::collection::vec(segment(), 0..4), any::<bool>()).prop_map(
+            |(segments, wildcard)| {
+                let mut path = String::new();
+                for (depth, segment) in segments.iter().enumerate() {
+                    match segment {
+                        Segment::Static(text) => {
+                            path.push('/');
+                            path.push_str(text);
+                        }
+                        Segment::Param => {
+                            path.push_str("/{p");
+                            path.push_str(&depth.to_string());
+                            path.push('}');
+                        }
+                    }
+                }
+                if wildcard {
+                    path.push_str("/{*rest}");
+                }
+                if path.is_empty() {
+                    path.push('/');
+                }
+                path
+            },
+        )
+    }
+
+    /// Route sets matchit and wayfind both accept, so both routers build.
+    fn route_set() -> impl Strategy<Value = Vec<String>> {
+        proptest::collection::btree_set(template(), 1..8).prop_map(|templates| {
+            let mut matchit = matchit::Router::new();
+            let mut wayfind = wayfind::Router::new();
+            templates
+                .into_iter()
+                .filter(|template| {
+                    let translated = template
+                        .replace("{*", "<*")
+                        .replace('{', "<")
+                        .replace('}', ">");
+                    matchit.insert(template.as_str(), ()).is_ok()
+                        && wayfind.insert(&translated, ()).is_ok()
+                })
+                .collect()
+        })
+    }
+
+    /// A value for a parameter: plain, percent-encoded, or with characters
+    /// that are easy to mishandle.
+    fn value() -> impl Strategy<Value = String> {
+        prop_oneof![
+            proptest::sample::select(STATICS).prop_map(str::to_owned),
+            "[a-z0-9]{1,6}",
+            Just("caf%C3%A9".to_owned()),
+            Just("a%20b".to_owned()),
+            Just("a%2Fb".to_owned()),
+            Just("x.json".to_owned()),
+        ]
+    }
+
+    /// Request paths: instances of the routes, plus arbitrary near misses.
+    fn requests(routes: Vec<String>) -> impl Strategy<Value = (Vec<String>, Vec<String>)> {
+        let instances = proptest::collection::vec(
+            (
+                proptest::sample::select(routes.clone()),
+                proptest::collection::vec(value(), 6),
+            ),
+            1..8,
+        )
+        .prop_map(|picks| {
+            picks
+                .into_iter()
+                .map(|(template, values)| instantiate(&template, &values))
+                .collect::<Vec<_>>()
+        });
+        let near_misses = proptest::collection::vec(
+            proptest::collection::vec(value(), 0..5)
+                .prop_map(|segments| format!("/{}", segments.join("/"))),
+            0..4,
+        );
+
+        (instances, near_misses).prop_map(move |(mut instances, near_misses)| {
+            instances.extend(near_misses);
+            (routes.clone(), instances)
+        })
+    }
+
+    /// `template` with its parameters replaced by `values`, in order.
+    fn instantiate(template: &str, values: &[String]) -> String {
+        let mut values = values.iter().cycle();
+        template
+            .split('/')
+            .map(|segment| {
+                if segment == "{*rest}" {
+                    let first = values.next().map_or("", String::as_str);
+                    let second = values.next().map_or("", String::as_str);
+                    format!("{first}/{second}")
+                } else if segment.starts_with('{') {
+                    values.next().cloned().unwrap_or_default()
+                } else {
+                    segment.to_owned()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig {
+            cases: 256,
+            failure_persistence: Some(Box::new(
+                proptest::test_runner::FileFailurePersistence::WithSource("proptest-regressions"),
+            )),
+            ..ProptestConfig::default()
+        })]
+
+        #[test]
+        fn same_as_axum((routes, requests) in route_set()
+            .prop_filter("needs a route", |routes| !routes.is_empty())
+            .prop_flat_map(requests))
+        {
+            assert_same(&routes, &requests);
+        }
+    }
+
+    // ==============================================================================
+    // Regressions
+    // ==============================================================================
+    //
+    // Fixed cases for shapes the generator is most likely to trip over, and
+    // for any counterexample proptest finds.
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| (*item).to_owned()).collect()
+    }
+
+    #[test]
+    fn static_beats_param_at_the_same_depth() {
+        assert_same(
+            &strings(&["/users/{p1}", "/users/a", "/{p0}/b"]),
+            &strings(&["/users/a", "/users/b", "/x/b", "/users/a%20b"]),
+        );
+    }
+
+    #[test]
+    fn wildcard_after_params() {
+        assert_same(
+            &strings(&["/{p0}/{*rest}", "/v1/{p1}", "/v1/users"]),
+            &strings(&["/v1/users", "/v1/x", "/v1/x/y", "/a/caf%C3%A9/a%2Fb", "/a"]),
+        );
+    }
+
+    #[test]
+    fn dotted_statics_and_root() {
+        assert_same(
+            &strings(&["/", "/x.json", "/{p0}", "/a/x.json"]),
+            &strings(&["/", "/x.json", "/x", "/a/x.json", "/a/x"]),
+        );
+    }
+}
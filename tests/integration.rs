@@ -8,7 +8,10 @@ mod tests {
     };
     use axum_wayfind::{
         Router,
-        extract::{MatchedPath, Path},
+        extract::{
+            MatchedAlias, MatchedPath, Path, PathParam, RawPathParams, rejection::ProblemJson,
+        },
+        inspect::AllowedMethods,
     };
     use http::StatusCode;
     use serde::{Deserialize, Serialize};
@@ -179,623 +182,2215 @@ mod tests {
         assert_eq!(get_body(resp).await, "hello world");
     }
 
-    // ==============================================================================
-    // MatchedPath
-    // ==============================================================================
-
     #[tokio::test]
-    async fn matched_path_extractor() {
-        let app = Router::new().route(
-            "/users/{id}",
-            get(|path: MatchedPath| async move { path.as_str().to_owned() }),
+    async fn dispatch_returns_named_future() {
+        use tower::Service;
+
+        let mut app = Router::new().route(
+            "/{a}/{b}",
+            get(|Path((a, b)): Path<(String, String)>| async move { format!("{a}|{b}") }),
         );
 
-        let resp = send_request(app, "GET", "/users/42", None).await;
-        assert_eq!(get_body(resp).await, "/users/{id}");
+        // Plain and percent-encoded values, and the fallback, all resolve
+        // through the same concrete future type.
+        for (uri, status, body) in [
+            ("/plain/caf%C3%A9", StatusCode::OK, "plain|café"),
+            ("/x%2Fy/z", StatusCode::OK, "x/y|z"),
+            ("/too/many/segments", StatusCode::NOT_FOUND, ""),
+        ] {
+            let req = http::Request::builder()
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .expect("valid request");
+            let future: axum_wayfind::RouterFuture = app.call(req);
+            let resp = future.await.expect("infallible");
+            assert_eq!(resp.status(), status);
+            assert_eq!(get_body(resp).await, body);
+        }
     }
 
     #[tokio::test]
-    async fn matched_path_in_extensions() {
-        let app = Router::new().route(
-            "/users/{id}",
-            get(|req: axum::extract::Request| async move {
-                req.extensions()
-                    .get::<MatchedPath>()
-                    .expect("MatchedPath in extensions")
-                    .as_str()
-                    .to_owned()
-            }),
-        );
+    async fn optional_path_param() {
+        async fn describe(path: Option<Path<u32>>) -> String {
+            path.map_or_else(|| "none".to_owned(), |Path(id)| format!("id {id}"))
+        }
 
-        let resp = send_request(app, "GET", "/users/42", None).await;
-        assert_eq!(get_body(resp).await, "/users/{id}");
-    }
+        let nested = Router::new()
+            .route("/known", get(describe))
+            .fallback(describe);
 
-    // ==============================================================================
-    // Root Route
-    // ==============================================================================
+        let app = Router::new()
+            .route("/users/{id}", get(describe))
+            .nest("/api", nested)
+            .fallback(describe);
 
-    #[tokio::test]
-    async fn root_route() {
-        let app = Router::new().route("/", get(|| async { "root" }));
+        let resp = send_request(app.clone(), "GET", "/users/42", None).await;
+        assert_eq!(get_body(resp).await, "id 42");
 
-        let resp = send_request(app, "GET", "/", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "root");
-    }
+        // A real deserialization error still rejects the request.
+        let resp = send_request(app.clone(), "GET", "/users/abc", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
-    // ==============================================================================
-    // Error Paths
-    // ==============================================================================
+        // Top-level fallback: no params extension at all.
+        let resp = send_request(app.clone(), "GET", "/missing", None).await;
+        assert_eq!(get_body(resp).await, "none");
+
+        // Matched route without params.
+        let resp = send_request(app.clone(), "GET", "/api/known", None).await;
+        assert_eq!(get_body(resp).await, "none");
+
+        // Nested fallback: only the private tail param was captured.
+        let resp = send_request(app, "GET", "/api/unknown/deeper", None).await;
+        assert_eq!(get_body(resp).await, "none");
+    }
 
     #[tokio::test]
-    async fn path_param_type_mismatch_returns_400() {
-        let app = Router::new().route(
-            "/users/{id}",
-            get(|Path(id): Path<u32>| async move { format!("user {id}") }),
-        );
+    async fn optional_params_without_captures_are_empty() {
+        async fn describe(
+            path: Option<Path<HashMap<String, String>>>,
+            raw: Option<RawPathParams>,
+        ) -> String {
+            format!(
+                "{:?} {:?}",
+                path.map(|Path(map)| map.len()),
+                raw.map(|raw| raw.len())
+            )
+        }
 
-        let resp = send_request(app, "GET", "/users/abc", None).await;
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let app = Router::new().route("/static", get(describe));
+
+        let resp = send_request(app, "GET", "/static", None).await;
+        assert_eq!(get_body(resp).await, "Some(0) Some(0)");
     }
 
     #[tokio::test]
-    async fn wrong_number_of_path_params() {
-        // Route has two params but we extract as a single `u32`.
-        // This is a programming error rather than bad user input, so
-        // axum's Path extractor returns 500 for parameter count mismatches.
+    async fn raw_path_params_keep_both_forms() {
         let app = Router::new().route(
-            "/users/{id}/posts/{post_id}",
-            get(|Path(id): Path<u32>| async move { format!("user {id}") }),
+            "/{bucket}/{*key}",
+            get(|params: RawPathParams| async move {
+                params
+                    .iter()
+                    .map(|p| {
+                        format!(
+                            "{}={}|{}",
+                            p.key(),
+                            p.raw(),
+                            p.decoded().unwrap_or("<invalid>")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(";")
+            }),
         );
 
-        let resp = send_request(app, "GET", "/users/1/posts/2", None).await;
-        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let resp = send_request(app.clone(), "GET", "/my%20bucket/a%2Fb/c", None).await;
+        assert_eq!(
+            get_body(resp).await,
+            "bucket=my%20bucket|my bucket;key=a%2Fb/c|a/b/c"
+        );
+
+        // Invalid UTF-8 in one parameter leaves the others readable.
+        let resp = send_request(app, "GET", "/ok/%ff", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "bucket=ok|ok;key=%ff|<invalid>");
     }
 
     #[tokio::test]
-    async fn enum_path_param() {
+    async fn byte_path_params_allow_invalid_utf8() {
         #[derive(Deserialize)]
-        #[serde(rename_all = "lowercase")]
-        enum Color {
-            Red,
-            Blue,
+        struct Object {
+            bucket: String,
+            key: bytes::Bytes,
         }
 
         let app = Router::new().route(
-            "/color/{color}",
-            get(|Path(color): Path<Color>| async move {
-                match color {
-                    Color::Red => "red",
-                    Color::Blue => "blue",
-                }
+            "/{bucket}/{*key}",
+            get(|Path(object): Path<Object>| async move {
+                format!("{} {:?}", object.bucket, object.key.as_ref())
             }),
         );
 
-        let resp = send_request(app.clone(), "GET", "/color/red", None).await;
+        let resp = send_request(app.clone(), "GET", "/photos/a/%ff%fe", None).await;
         assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "red");
+        assert_eq!(get_body(resp).await, "photos [97, 47, 255, 254]");
 
-        let resp = send_request(app, "GET", "/color/green", None).await;
+        // A string parameter that isn't valid UTF-8 is still rejected.
+        let resp = send_request(app, "GET", "/%ff/key", None).await;
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            get_body(resp).await,
+            "Invalid URL: Invalid UTF-8 in `bucket`"
+        );
     }
 
-    // ==============================================================================
-    // Fallback
-    // ==============================================================================
-
     #[tokio::test]
-    async fn default_404() {
-        let app = Router::new().route("/exists", get(|| async { "ok" }));
+    async fn decoded_params_are_shared_across_extractors() {
+        use axum_wayfind::extract::path::PathRejection;
+
+        async fn handler(raw: RawPathParams, path: Result<Path<String>, PathRejection>) -> String {
+            let first = raw.get("name").and_then(|p| p.decoded()).map(str::to_owned);
+            match path {
+                Ok(Path(name)) => format!("{first:?} {name}"),
+                Err(rejection) => format!("{first:?} {rejection}"),
+            }
+        }
 
-        let resp = send_request(app, "GET", "/not-here", None).await;
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let app = Router::new().route("/{name}", get(handler));
+
+        let resp = send_request(app.clone(), "GET", "/caf%C3%A9", None).await;
+        assert_eq!(get_body(resp).await, "Some(\"café\") café");
+
+        // A cached failure still rejects as `InvalidUtf8InPathParam`.
+        let resp = send_request(app, "GET", "/%ff", None).await;
+        assert_eq!(get_body(resp).await, "None Invalid UTF-8 in `name`");
     }
 
     #[tokio::test]
-    async fn custom_fallback() {
+    async fn lossy_utf8_path_params() {
         let app = Router::new()
-            .route("/exists", get(|| async { "ok" }))
-            .fallback(|| async { (StatusCode::IM_A_TEAPOT, "teapot") });
+            .route("/{key}", get(|Path(key): Path<String>| async move { key }))
+            .lossy_utf8_path_params();
 
-        let resp = send_request(app, "GET", "/not-here", None).await;
-        assert_eq!(resp.status(), StatusCode::IM_A_TEAPOT);
-        assert_eq!(get_body(resp).await, "teapot");
+        let resp = send_request(app, "GET", "/a%ffb", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "a\u{fffd}b");
     }
 
     #[tokio::test]
-    async fn fallback_service() {
-        use tower::service_fn;
-
-        let svc = service_fn(|_req: axum::extract::Request| async {
-            Ok::<_, std::convert::Infallible>(axum::response::IntoResponse::into_response((
-                StatusCode::SERVICE_UNAVAILABLE,
-                "maintenance",
-            )))
-        });
+    async fn lossy_utf8_path_params_stay_with_their_routes() {
+        let echo = || get(|Path(value): Path<String>| async move { value });
+        let lossy = Router::new().route("/{v}", echo()).lossy_utf8_path_params();
 
         let app = Router::new()
-            .route("/exists", get(|| async { "ok" }))
-            .fallback_service(svc);
+            .route("/s/{v}", echo())
+            .nest("/lossy", lossy);
 
-        let resp = send_request(app, "GET", "/not-here", None).await;
-        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
-        assert_eq!(get_body(resp).await, "maintenance");
-    }
+        let resp = send_request(app.clone(), "GET", "/lossy/%FF", None).await;
+        assert_eq!(get_body(resp).await, "\u{fffd}");
 
-    // ==============================================================================
-    // State
-    // ==============================================================================
+        let resp = send_request(app, "GET", "/s/%FF", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let app = Router::new().route("/s/{v}", echo()).merge(
+            Router::new()
+                .route("/m/{v}", echo())
+                .lossy_utf8_path_params(),
+        );
+
+        let resp = send_request(app.clone(), "GET", "/m/%FF", None).await;
+        assert_eq!(get_body(resp).await, "\u{fffd}");
+
+        let resp = send_request(app, "GET", "/s/%FF", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
 
     #[tokio::test]
-    async fn with_state() {
-        #[derive(Clone)]
-        struct AppState {
-            greeting: String,
+    async fn wildcard_segments_into_vec() {
+        #[derive(Deserialize)]
+        struct Archive {
+            version: u32,
+            path: Vec<String>,
         }
 
         let app = Router::new()
             .route(
-                "/greet/{name}",
-                get(
-                    |State(state): State<AppState>, Path(name): Path<String>| async move {
-                        format!("{}, {name}!", state.greeting)
-                    },
-                ),
+                "/files/{*path}",
+                get(|Path(segments): Path<Vec<String>>| async move { segments.join("|") }),
             )
-            .with_state(AppState {
-                greeting: "Hello".to_owned(),
-            });
+            .route(
+                "/archive/{version}/{*path}",
+                get(|Path(archive): Path<Archive>| async move {
+                    format!("v{} {}", archive.version, archive.path.join("|"))
+                }),
+            )
+            .route(
+                "/numbers/{*path}",
+                get(|Path(numbers): Path<Vec<u32>>| async move {
+                    numbers.iter().sum::<u32>().to_string()
+                }),
+            );
 
-        let resp = send_request(app, "GET", "/greet/Alice", None).await;
-        assert_eq!(get_body(resp).await, "Hello, Alice!");
-    }
+        // `%2F` stays inside its segment instead of splitting it.
+        let resp = send_request(app.clone(), "GET", "/files/docs/a%2Fb.txt", None).await;
+        assert_eq!(get_body(resp).await, "docs|a/b.txt");
 
-    // ==============================================================================
-    // JSON
-    // ==============================================================================
+        let resp = send_request(app.clone(), "GET", "/archive/2/src/my%20lib.rs", None).await;
+        assert_eq!(get_body(resp).await, "v2 src|my lib.rs");
 
-    #[tokio::test]
-    async fn json_request_response() {
-        #[derive(Deserialize, Serialize)]
-        struct User {
-            name: String,
-            age: u32,
-        }
+        let resp = send_request(app.clone(), "GET", "/numbers/1/2/3", None).await;
+        assert_eq!(get_body(resp).await, "6");
 
-        let app = Router::new().route(
-            "/users",
-            post(|Json(user): Json<User>| async move {
-                Json(User {
-                    name: user.name.to_uppercase(),
-                    age: user.age,
-                })
-            }),
+        let resp = send_request(app, "GET", "/numbers/1/x", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            get_body(resp).await,
+            "Invalid URL: Cannot parse `path` with value `x` to a `u32`: invalid digit found in string"
         );
+    }
 
-        let body = serde_json::to_string(&serde_json::json!({
-            "name": "alice",
-            "age": 30
-        }))
-        .expect("serialize");
+    #[tokio::test]
+    async fn single_param_key_value_pairs_into_vec() {
+        async fn pairs(Path(pairs): Path<Vec<(String, String)>>) -> String {
+            pairs
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join("&")
+        }
 
-        let resp = send_request(app, "POST", "/users", Some(body)).await;
+        let app = Router::new()
+            .route("/users/{id}", get(pairs))
+            .route("/files/{*path}", get(pairs));
+
+        let resp = send_request(app.clone(), "GET", "/users/42", None).await;
         assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "id=42");
 
-        let body = get_body(resp).await;
-        let user: User = serde_json::from_str(&body).expect("deserialize");
-        assert_eq!(user.name, "ALICE");
-        assert_eq!(user.age, 30);
+        // A wildcard only splits into segments for scalar elements.
+        let resp = send_request(app, "GET", "/files/docs/a.txt", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "path=docs/a.txt");
     }
 
-    // ==============================================================================
-    // Merge
-    // ==============================================================================
-
     #[tokio::test]
-    async fn merge_routers() {
-        let users = Router::new().route("/users", get(|| async { "users" }));
-        let posts = Router::new().route("/posts", get(|| async { "posts" }));
-
-        let app = users.merge(posts);
+    async fn nested_and_flattened_path_structs() {
+        #[derive(Deserialize)]
+        struct Tenant {
+            tenant: String,
+            region: String,
+        }
 
-        let resp = send_request(app.clone(), "GET", "/users", None).await;
-        assert_eq!(get_body(resp).await, "users");
+        #[derive(Deserialize)]
+        struct Scoped(Tenant);
 
-        let resp = send_request(app, "GET", "/posts", None).await;
-        assert_eq!(get_body(resp).await, "posts");
-    }
+        #[derive(Deserialize)]
+        struct Flattened {
+            #[serde(flatten)]
+            common: Tenant,
+            id: u32,
+        }
 
-    // ==============================================================================
-    // Layers
-    // ==============================================================================
+        #[derive(Deserialize)]
+        struct Nested {
+            scope: Scoped,
+            id: u32,
+        }
 
-    #[tokio::test]
-    async fn route_layer_applies_to_routes_only() {
-        // `route_layer` should apply the layer to routes but not the fallback.
-        // We use SetResponseHeader to add a custom header so we can observe the
-        // layer's effect.
-        use tower_http::set_header::SetResponseHeaderLayer;
+        #[derive(Deserialize)]
+        struct Unsupported {
+            id: u32,
+            #[allow(dead_code)]
+            page: u32,
+        }
 
         let app = Router::new()
-            .route("/exists", get(|| async { "ok" }))
-            .route_layer(SetResponseHeaderLayer::overriding(
-                http::header::HeaderName::from_static("x-custom"),
-                http::HeaderValue::from_static("layered"),
-            ));
+            .route(
+                "/flat/{region}/{tenant}/items/{id}",
+                get(|Path(p): Path<Flattened>| async move {
+                    format!("{}/{} {}", p.common.region, p.common.tenant, p.id)
+                }),
+            )
+            .route(
+                "/nested/{region}/{tenant}/items/{id}",
+                get(|Path(p): Path<Nested>| async move {
+                    let Scoped(tenant) = p.scope;
+                    format!("{}/{} {}", tenant.region, tenant.tenant, p.id)
+                }),
+            )
+            .route(
+                "/unsupported/{tenant}/items/{id}",
+                get(|Path(p): Path<Unsupported>| async move { p.id.to_string() }),
+            );
 
-        // Route should have the header.
-        let resp = send_request(app.clone(), "GET", "/exists", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(
-            resp.headers()
-                .get("x-custom")
-                .expect("header")
-                .to_str()
-                .expect("str"),
-            "layered"
-        );
+        let resp = send_request(app.clone(), "GET", "/flat/eu/acme/items/7", None).await;
+        assert_eq!(get_body(resp).await, "eu/acme 7");
 
-        // Fallback (404) should NOT have the header.
-        let resp = send_request(app, "GET", "/not-here", None).await;
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
-        assert!(resp.headers().get("x-custom").is_none());
+        let resp = send_request(app.clone(), "GET", "/nested/eu/acme/items/7", None).await;
+        assert_eq!(get_body(resp).await, "eu/acme 7");
+
+        // A scalar field with no parameter is still a missing field.
+        let resp = send_request(app, "GET", "/unsupported/acme/items/7", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(get_body(resp).await, "Invalid URL: missing field `page`");
     }
 
     #[tokio::test]
-    async fn layer_applies_to_routes_and_fallback() {
-        use tower_http::set_header::SetResponseHeaderLayer;
+    async fn default_fields_next_to_unclaimed_params() {
+        #[derive(Deserialize)]
+        struct Page {
+            id: u32,
+            #[serde(default)]
+            page: u32,
+        }
 
-        let app = Router::new()
-            .route("/exists", get(|| async { "ok" }))
-            .layer(SetResponseHeaderLayer::overriding(
-                http::header::HeaderName::from_static("x-custom"),
-                http::HeaderValue::from_static("layered"),
-            ));
+        #[derive(Deserialize)]
+        struct Org {
+            org: String,
+        }
 
-        // Route should have the header.
-        let resp = send_request(app.clone(), "GET", "/exists", None).await;
+        #[derive(Deserialize)]
+        struct Scoped {
+            scope: Org,
+            #[serde(default)]
+            page: u32,
+            id: u32,
+        }
+
+        let app = Router::new()
+            .route(
+                "/plain/{org}/{id}",
+                get(|Path(p): Path<Page>| async move { format!("{} {}", p.id, p.page) }),
+            )
+            .route(
+                "/scoped/{org}/{id}",
+                get(|Path(p): Path<Scoped>| async move {
+                    format!("{} {} {}", p.scope.org, p.id, p.page)
+                }),
+            );
+
+        let resp = send_request(app.clone(), "GET", "/plain/acme/5", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "5 0");
+
+        let resp = send_request(app, "GET", "/scoped/acme/5", None).await;
         assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "acme 5 0");
+    }
+
+    #[tokio::test]
+    async fn problem_json_rejections() {
+        #[derive(Deserialize)]
+        struct Post {
+            user: String,
+            id: u32,
+        }
+
+        let app = Router::new()
+            .route(
+                "/users/{id}",
+                get(|Path(id): Path<u32>| async move { id.to_string() }),
+            )
+            .route(
+                "/users/{user}/posts/{id}",
+                get(|Path(post): Path<Post>| async move { format!("{} {}", post.user, post.id) }),
+            )
+            .fallback(|path: MatchedPath| async move { path.as_str().to_owned() })
+            .rejection_formatter(ProblemJson);
+
+        let resp = send_request(app.clone(), "GET", "/users/abc", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(resp.headers()["content-type"], "application/problem+json");
+        let problem: serde_json::Value =
+            serde_json::from_str(&get_body(resp).await).expect("valid JSON");
         assert_eq!(
-            resp.headers()
-                .get("x-custom")
-                .expect("header")
-                .to_str()
-                .expect("str"),
-            "layered"
+            problem,
+            serde_json::json!({
+                "type": "about:blank",
+                "title": "Bad Request",
+                "status": 400,
+                "detail": "Invalid URL: Cannot parse `abc` to a `u32`: invalid digit found in string",
+                "value": "abc",
+                "expected_type": "u32",
+                "message": "invalid digit found in string",
+            })
         );
 
-        // Fallback should also have the header.
-        let resp = send_request(app, "GET", "/not-here", None).await;
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        // Struct fields carry their key.
+        let resp = send_request(app.clone(), "GET", "/users/ann/posts/abc", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let problem: serde_json::Value =
+            serde_json::from_str(&get_body(resp).await).expect("valid JSON");
         assert_eq!(
-            resp.headers()
-                .get("x-custom")
-                .expect("header")
-                .to_str()
-                .expect("str"),
-            "layered"
+            problem,
+            serde_json::json!({
+                "type": "about:blank",
+                "title": "Bad Request",
+                "status": 400,
+                "detail": "Invalid URL: Cannot parse `id` with value `abc` to a `u32`: invalid digit found in string",
+                "key": "id",
+                "value": "abc",
+                "expected_type": "u32",
+                "message": "invalid digit found in string",
+            })
         );
-    }
 
-    // ==============================================================================
-    // Merge (additional)
-    // ==============================================================================
+        // Fallbacks get the formatter too.
+        let resp = send_request(app, "GET", "/nope", None).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let problem: serde_json::Value =
+            serde_json::from_str(&get_body(resp).await).expect("valid JSON");
+        assert_eq!(problem["detail"], "Matched path is not available");
+    }
 
     #[tokio::test]
-    async fn merge_routers_with_overlapping_methods() {
-        // Two routers register different methods on the same path. After merge,
-        // both methods should work.
-        let router_get = Router::new().route("/item", get(|| async { "get" }));
-        let router_post = Router::new().route("/item", post(|| async { "post" }));
+    async fn rejection_formatter_stays_with_its_routes() {
+        let numeric = || get(|Path(id): Path<u32>| async move { id.to_string() });
+        let problem = |routes: Router| routes.rejection_formatter(ProblemJson);
 
-        let app = router_get.merge(router_post);
+        let app = Router::new()
+            .route("/outer/{id}", numeric())
+            .nest("/nested", problem(Router::new().route("/{id}", numeric())))
+            .merge(problem(Router::new().route("/merged/{id}", numeric())))
+            .fallback(|path: MatchedPath| async move { path.as_str().to_owned() });
+
+        for uri in ["/nested/abc", "/merged/abc"] {
+            let resp = send_request(app.clone(), "GET", uri, None).await;
+            assert_eq!(resp.headers()["content-type"], "application/problem+json");
+        }
 
-        let resp = send_request(app.clone(), "GET", "/item", None).await;
-        assert_eq!(get_body(resp).await, "get");
+        let resp = send_request(app.clone(), "GET", "/outer/abc", None).await;
+        assert_eq!(resp.headers()["content-type"], "text/plain; charset=utf-8");
 
-        let resp = send_request(app, "POST", "/item", None).await;
-        assert_eq!(get_body(resp).await, "post");
+        let resp = send_request(app, "GET", "/nope", None).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(resp.headers()["content-type"], "text/plain; charset=utf-8");
     }
 
-    // ==============================================================================
-    // Nesting
-    // ==============================================================================
+    #[cfg(feature = "validation")]
+    #[tokio::test]
+    async fn validated_path_reports_field_errors() {
+        use axum_wayfind::extract::{
+            ValidatedPath,
+            path::PathRejection,
+            validated_path::{PathValidationErrors, ValidatePath},
+        };
+
+        #[derive(Deserialize)]
+        struct Page {
+            slug: String,
+            number: u32,
+        }
+
+        impl ValidatePath for Page {
+            fn validate(&self) -> Result<(), PathValidationErrors> {
+                let mut errors = PathValidationErrors::new();
+                if !(1..=100).contains(&self.number) {
+                    errors.add("number", self.number, "u32", "must be between 1 and 100");
+                }
+                if !self
+                    .slug
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c == '-')
+                {
+                    errors.add(
+                        "slug",
+                        &self.slug,
+                        "String",
+                        "must be lowercase letters and dashes",
+                    );
+                }
+                errors.into_result()
+            }
+        }
+
+        let app = Router::new().route(
+            "/docs/{slug}/{number}",
+            get(|ValidatedPath(page): ValidatedPath<Page>| async move {
+                format!("{} {}", page.slug, page.number)
+            }),
+        );
+
+        let resp = send_request(app.clone(), "GET", "/docs/intro/3", None).await;
+        assert_eq!(get_body(resp).await, "intro 3");
+
+        let resp = send_request(app.clone(), "GET", "/docs/Intro/0", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            get_body(resp).await,
+            "Invalid URL: Cannot parse `number` with value `0` to a `u32`: must be between 1 and 100; \
+             Cannot parse `slug` with value `Intro` to a `String`: must be lowercase letters and dashes"
+        );
+
+        // Deserialization failures are rejected as `Path` rejects them.
+        let resp = send_request(app.clone(), "GET", "/docs/intro/x", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            get_body(resp).await,
+            "Invalid URL: Cannot parse `number` with value `x` to a `u32`: invalid digit found in string"
+        );
+
+        let app = app.rejection_formatter(ProblemJson);
+        let resp = send_request(app, "GET", "/docs/intro/0", None).await;
+        let problem: serde_json::Value =
+            serde_json::from_str(&get_body(resp).await).expect("valid JSON");
+        assert_eq!(
+            problem["errors"],
+            serde_json::json!([{
+                "key": "number",
+                "value": "0",
+                "expected_type": "u32",
+                "message": "must be between 1 and 100",
+            }])
+        );
+
+        // Validation failures share `Path`'s rejection type.
+        let app = Router::new().route(
+            "/docs/{slug}/{number}",
+            get(
+                |page: Result<ValidatedPath<Page>, PathRejection>| async move {
+                    match page {
+                        Ok(_) => "ok".to_owned(),
+                        Err(PathRejection::FailedToValidatePathParams(inner)) => {
+                            format!("{} invalid", inner.errors().errors().len())
+                        }
+                        Err(other) => other.to_string(),
+                    }
+                },
+            ),
+        );
+        let resp = send_request(app, "GET", "/docs/Intro/0", None).await;
+        assert_eq!(get_body(resp).await, "2 invalid");
+    }
 
     #[tokio::test]
-    async fn nest_basic_dispatch() {
-        let api = Router::new()
-            .route("/users", get(|| async { "users" }))
-            .route("/posts", get(|| async { "posts" }));
+    async fn path_param_parses_with_from_str() {
+        #[derive(Debug)]
+        struct Slug(String);
+
+        impl std::str::FromStr for Slug {
+            type Err = &'static str;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if s.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+                    Ok(Self(s.to_owned()))
+                } else {
+                    Err("not a slug")
+                }
+            }
+        }
 
-        let app = Router::new().nest("/api", api);
+        axum_wayfind::param_name!(SlugParam = "slug");
+        axum_wayfind::param_name!(Missing = "missing");
 
-        let resp = send_request(app.clone(), "GET", "/api/users", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "users");
+        let app = Router::new()
+            .route(
+                "/posts/{slug}",
+                get(|slug: PathParam<SlugParam, Slug>| async move { slug.into_inner().0 }),
+            )
+            .route(
+                "/other/{slug}",
+                get(|_: PathParam<Missing, String>| async {}),
+            );
 
-        let resp = send_request(app.clone(), "GET", "/api/posts", None).await;
-        assert_eq!(get_body(resp).await, "posts");
+        let resp = send_request(app.clone(), "GET", "/posts/hello-world", None).await;
+        assert_eq!(get_body(resp).await, "hello-world");
 
-        // Non-nested path should 404.
-        let resp = send_request(app, "GET", "/users", None).await;
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let resp = send_request(app.clone(), "GET", "/posts/Hello", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = get_body(resp).await;
+        assert!(
+            body.starts_with("Invalid URL: Cannot parse `slug` with value `Hello` to a `"),
+            "{body}"
+        );
+        assert!(body.ends_with("Slug`: not a slug"), "{body}");
+
+        let resp = send_request(app, "GET", "/other/x", None).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(get_body(resp).await, "No path parameter named `missing`");
     }
 
+    #[cfg(feature = "macros")]
     #[tokio::test]
-    async fn nest_path_extraction() {
-        let api = Router::new().route(
-            "/users/{id}",
-            get(|Path(id): Path<u32>| async move { format!("user {id}") }),
+    async fn route_macro_checks_path_fields() {
+        use axum_wayfind::{
+            checked::{PathField, PathFields},
+            route,
+        };
+
+        #[derive(Deserialize, PathFields)]
+        struct Tenant {
+            tenant: String,
+        }
+
+        #[derive(Deserialize, PathFields)]
+        struct Params {
+            #[path_fields(nested)]
+            scope: Tenant,
+            #[serde(rename = "id")]
+            user_id: u32,
+            #[serde(rename(serialize = "team", deserialize = "org"))]
+            org_slug: String,
+            format: Option<String>,
+        }
+
+        assert_eq!(
+            Params::FIELDS,
+            &[
+                PathField::Nested(&[PathField::Param("tenant")]),
+                PathField::Param("id"),
+                PathField::Param("org"),
+            ]
         );
 
-        let app = Router::new().nest("/api", api);
+        let app = Router::new().route(
+            route!("/{tenant}/{org}/users/{id}", Params),
+            get(|Path(params): Path<Params>| async move {
+                format!(
+                    "{} {} {} {:?}",
+                    params.scope.tenant, params.org_slug, params.user_id, params.format
+                )
+            }),
+        );
+        assert_eq!(route!("/files/{name}.{ext}"), "/files/{name}.{ext}");
 
-        let resp = send_request(app, "GET", "/api/users/42", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "user 42");
+        let resp = send_request(app, "GET", "/acme/eng/users/7", None).await;
+        assert_eq!(get_body(resp).await, "acme eng 7 None");
+    }
+
+    #[test]
+    fn macro_template_parser_matches_router() {
+        // `axum-wayfind-macros` keeps its own copy of the template parser.
+        assert_eq!(
+            include_str!("../macros/src/syntax.rs"),
+            include_str!("../src/syntax.rs"),
+            "macros/src/syntax.rs must stay identical to src/syntax.rs"
+        );
     }
 
+    #[cfg(feature = "typed-routing")]
     #[tokio::test]
-    async fn nest_matched_path() {
-        let api = Router::new().route(
+    async fn typed_paths_render_and_extract() {
+        use axum_wayfind::typed::WayfindTypedPath;
+
+        #[derive(WayfindTypedPath, Deserialize)]
+        #[typed_path("/files/{name}.{ext}")]
+        struct FilePath {
+            name: String,
+            ext: String,
+        }
+
+        #[derive(WayfindTypedPath, Deserialize)]
+        #[typed_path("/{*repo}/tree/{branch}")]
+        struct TreePath {
+            repo: String,
+            branch: String,
+        }
+
+        #[derive(WayfindTypedPath, Deserialize)]
+        #[typed_path("/users/{id}")]
+        struct UserPath(u32);
+
+        #[derive(WayfindTypedPath, Deserialize)]
+        #[typed_path("/teams/{team}/members/{id}")]
+        struct MemberPath {
+            team: String,
+            #[serde(rename = "id")]
+            member_id: u32,
+        }
+
+        assert_eq!(FilePath::PATH, "/files/{name}.{ext}");
+        let file = FilePath {
+            name: "q3 report".to_owned(),
+            ext: "pdf".to_owned(),
+        };
+        assert_eq!(file.to_string(), "/files/q3%20report.pdf");
+        let tree = TreePath {
+            repo: "org/my repo".to_owned(),
+            branch: "feat/x".to_owned(),
+        };
+        assert_eq!(tree.to_uri(), "/org/my%20repo/tree/feat%2Fx");
+        assert_eq!(UserPath(7).to_string(), "/users/7");
+        let member = MemberPath {
+            team: "core".to_owned(),
+            member_id: 3,
+        };
+        assert_eq!(member.to_string(), "/teams/core/members/3");
+
+        let app = Router::new()
+            .typed_get(|FilePath { name, ext }: FilePath| async move { format!("{name} {ext}") })
+            .typed_get(
+                |TreePath { repo, branch }: TreePath| async move { format!("{repo} {branch}") },
+            )
+            .typed_get(|UserPath(id): UserPath| async move { id.to_string() })
+            .typed_get(|MemberPath { team, member_id }: MemberPath| async move {
+                format!("{team} {member_id}")
+            })
+            .typed_post(|_: UserPath, body: String| async move { body });
+
+        // Rendered URLs round-trip through extraction.
+        let resp = send_request(app.clone(), "GET", &file.to_string(), None).await;
+        assert_eq!(get_body(resp).await, "q3 report pdf");
+        let resp = send_request(app.clone(), "GET", &tree.to_string(), None).await;
+        assert_eq!(get_body(resp).await, "org/my repo feat/x");
+        let resp = send_request(app.clone(), "GET", &member.to_string(), None).await;
+        assert_eq!(get_body(resp).await, "core 3");
+        let resp = send_request(app.clone(), "POST", "/users/7", Some("hi".to_owned())).await;
+        assert_eq!(get_body(resp).await, "hi");
+
+        let resp = send_request(app, "GET", "/users/abc", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // ==============================================================================
+    // MatchedPath
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn matched_path_extractor() {
+        let app = Router::new().route(
             "/users/{id}",
             get(|path: MatchedPath| async move { path.as_str().to_owned() }),
         );
 
-        let app = Router::new().nest("/api", api);
-
-        let resp = send_request(app, "GET", "/api/users/42", None).await;
-        assert_eq!(get_body(resp).await, "/api/users/{id}");
+        let resp = send_request(app, "GET", "/users/42", None).await;
+        assert_eq!(get_body(resp).await, "/users/{id}");
     }
 
     #[tokio::test]
-    async fn nest_strips_prefix_from_uri() {
-        // Verify that handlers see the stripped URI path, not the full one.
-        let api = Router::new().route(
-            "/resource",
-            get(|req: axum::extract::Request| async move { req.uri().path().to_owned() }),
+    async fn matched_path_in_extensions() {
+        let app = Router::new().route(
+            "/users/{id}",
+            get(|req: axum::extract::Request| async move {
+                req.extensions()
+                    .get::<MatchedPath>()
+                    .expect("MatchedPath in extensions")
+                    .as_str()
+                    .to_owned()
+            }),
         );
 
-        let app = Router::new().nest("/api/v1", api);
-
-        let resp = send_request(app, "GET", "/api/v1/resource", None).await;
-        assert_eq!(get_body(resp).await, "/resource");
+        let resp = send_request(app, "GET", "/users/42", None).await;
+        assert_eq!(get_body(resp).await, "/users/{id}");
     }
 
     #[tokio::test]
-    async fn nest_with_outer_routes() {
-        let api = Router::new().route("/items", get(|| async { "items" }));
-
+    async fn route_aliases_share_one_route() {
+        let handler = |path: MatchedPath, alias: MatchedAlias, Path(id): Path<u32>| async move {
+            format!("{} {} {id}", path.as_str(), alias.as_str())
+        };
         let app = Router::new()
-            .route("/health", get(|| async { "ok" }))
-            .nest("/api", api);
+            .route_aliases(&["/users/{id}", "/u/{id}", "/members/{id}"], get(handler))
+            .route_aliases(&["/u/{id}"], post(|| async { "created" }));
+
+        let resp = send_request(app.clone(), "GET", "/users/7", None).await;
+        assert_eq!(get_body(resp).await, "/users/{id} /users/{id} 7");
+
+        let resp = send_request(app.clone(), "GET", "/u/7", None).await;
+        assert_eq!(get_body(resp).await, "/users/{id} /u/{id} 7");
+
+        // Methods added under any alias join the shared route.
+        let resp = send_request(app.clone(), "POST", "/members/7", None).await;
+        assert_eq!(get_body(resp).await, "created");
+
+        let canonical = app.at("/users/7").expect("canonical matches");
+        let alias = app.at("/members/7").expect("alias matches");
+        assert_eq!(canonical.route_id(), alias.route_id());
+        assert_eq!(alias.template(), "/users/{id}");
+
+        // Aliases survive nesting, under the prefix.
+        let app = Router::new().nest("/api", app);
+        let resp = send_request(app.clone(), "GET", "/api/u/7", None).await;
+        assert_eq!(get_body(resp).await, "/api/users/{id} /api/u/{id} 7");
+        assert!(
+            app.to_string()
+                .contains("/api/u/{id} -> /api/u/<id> (alias)")
+        );
+    }
 
-        let resp = send_request(app.clone(), "GET", "/health", None).await;
-        assert_eq!(get_body(resp).await, "ok");
+    // ==============================================================================
+    // Root Route
+    // ==============================================================================
 
-        let resp = send_request(app, "GET", "/api/items", None).await;
-        assert_eq!(get_body(resp).await, "items");
+    #[tokio::test]
+    async fn root_route() {
+        let app = Router::new().route("/", get(|| async { "root" }));
+
+        let resp = send_request(app, "GET", "/", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "root");
     }
 
+    // ==============================================================================
+    // Error Paths
+    // ==============================================================================
+
     #[tokio::test]
-    async fn nest_with_state() {
-        #[derive(Clone)]
-        struct AppState {
-            prefix: String,
-        }
+    async fn path_param_type_mismatch_returns_400() {
+        let app = Router::new().route(
+            "/users/{id}",
+            get(|Path(id): Path<u32>| async move { format!("user {id}") }),
+        );
 
-        let api =
-            Router::new().route(
-                "/greet",
-                get(|State(state): State<AppState>| async move {
-                    format!("hello from {}", state.prefix)
-                }),
-            );
+        let resp = send_request(app, "GET", "/users/abc", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
 
-        let app = Router::new().nest("/api", api).with_state(AppState {
-            prefix: "api".to_owned(),
-        });
+    #[tokio::test]
+    async fn wrong_number_of_path_params() {
+        // Route has two params but we extract as a single `u32`.
+        // This is a programming error rather than bad user input, so
+        // axum's Path extractor returns 500 for parameter count mismatches.
+        let app = Router::new().route(
+            "/users/{id}/posts/{post_id}",
+            get(|Path(id): Path<u32>| async move { format!("user {id}") }),
+        );
+
+        let resp = send_request(app, "GET", "/users/1/posts/2", None).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn enum_path_param() {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Color {
+            Red,
+            Blue,
+        }
+
+        let app = Router::new().route(
+            "/color/{color}",
+            get(|Path(color): Path<Color>| async move {
+                match color {
+                    Color::Red => "red",
+                    Color::Blue => "blue",
+                }
+            }),
+        );
+
+        let resp = send_request(app.clone(), "GET", "/color/red", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "red");
+
+        let resp = send_request(app, "GET", "/color/green", None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // ==============================================================================
+    // Fallback
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn default_404() {
+        let app = Router::new().route("/exists", get(|| async { "ok" }));
+
+        let resp = send_request(app, "GET", "/not-here", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn custom_fallback() {
+        let app = Router::new()
+            .route("/exists", get(|| async { "ok" }))
+            .fallback(|| async { (StatusCode::IM_A_TEAPOT, "teapot") });
+
+        let resp = send_request(app, "GET", "/not-here", None).await;
+        assert_eq!(resp.status(), StatusCode::IM_A_TEAPOT);
+        assert_eq!(get_body(resp).await, "teapot");
+    }
+
+    #[tokio::test]
+    async fn fallback_service() {
+        use tower::service_fn;
+
+        let svc = service_fn(|_req: axum::extract::Request| async {
+            Ok::<_, std::convert::Infallible>(axum::response::IntoResponse::into_response((
+                StatusCode::SERVICE_UNAVAILABLE,
+                "maintenance",
+            )))
+        });
+
+        let app = Router::new()
+            .route("/exists", get(|| async { "ok" }))
+            .fallback_service(svc);
+
+        let resp = send_request(app, "GET", "/not-here", None).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(get_body(resp).await, "maintenance");
+    }
+
+    // ==============================================================================
+    // State
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn with_state() {
+        #[derive(Clone)]
+        struct AppState {
+            greeting: String,
+        }
+
+        let app = Router::new()
+            .route(
+                "/greet/{name}",
+                get(
+                    |State(state): State<AppState>, Path(name): Path<String>| async move {
+                        format!("{}, {name}!", state.greeting)
+                    },
+                ),
+            )
+            .with_state(AppState {
+                greeting: "Hello".to_owned(),
+            });
+
+        let resp = send_request(app, "GET", "/greet/Alice", None).await;
+        assert_eq!(get_body(resp).await, "Hello, Alice!");
+    }
+
+    // ==============================================================================
+    // JSON
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn json_request_response() {
+        #[derive(Deserialize, Serialize)]
+        struct User {
+            name: String,
+            age: u32,
+        }
+
+        let app = Router::new().route(
+            "/users",
+            post(|Json(user): Json<User>| async move {
+                Json(User {
+                    name: user.name.to_uppercase(),
+                    age: user.age,
+                })
+            }),
+        );
+
+        let body = serde_json::to_string(&serde_json::json!({
+            "name": "alice",
+            "age": 30
+        }))
+        .expect("serialize");
+
+        let resp = send_request(app, "POST", "/users", Some(body)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = get_body(resp).await;
+        let user: User = serde_json::from_str(&body).expect("deserialize");
+        assert_eq!(user.name, "ALICE");
+        assert_eq!(user.age, 30);
+    }
+
+    // ==============================================================================
+    // Merge
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn merge_routers() {
+        let users = Router::new().route("/users", get(|| async { "users" }));
+        let posts = Router::new().route("/posts", get(|| async { "posts" }));
+
+        let app = users.merge(posts);
+
+        let resp = send_request(app.clone(), "GET", "/users", None).await;
+        assert_eq!(get_body(resp).await, "users");
+
+        let resp = send_request(app, "GET", "/posts", None).await;
+        assert_eq!(get_body(resp).await, "posts");
+    }
+
+    // ==============================================================================
+    // Layers
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn route_layer_applies_to_routes_only() {
+        // `route_layer` should apply the layer to routes but not the fallback.
+        // We use SetResponseHeader to add a custom header so we can observe the
+        // layer's effect.
+        use tower_http::set_header::SetResponseHeaderLayer;
+
+        let app = Router::new()
+            .route("/exists", get(|| async { "ok" }))
+            .route_layer(SetResponseHeaderLayer::overriding(
+                http::header::HeaderName::from_static("x-custom"),
+                http::HeaderValue::from_static("layered"),
+            ));
+
+        // Route should have the header.
+        let resp = send_request(app.clone(), "GET", "/exists", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()
+                .get("x-custom")
+                .expect("header")
+                .to_str()
+                .expect("str"),
+            "layered"
+        );
+
+        // Fallback (404) should NOT have the header.
+        let resp = send_request(app, "GET", "/not-here", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert!(resp.headers().get("x-custom").is_none());
+    }
+
+    #[tokio::test]
+    async fn layer_applies_to_routes_and_fallback() {
+        use tower_http::set_header::SetResponseHeaderLayer;
+
+        let app = Router::new()
+            .route("/exists", get(|| async { "ok" }))
+            .layer(SetResponseHeaderLayer::overriding(
+                http::header::HeaderName::from_static("x-custom"),
+                http::HeaderValue::from_static("layered"),
+            ));
+
+        // Route should have the header.
+        let resp = send_request(app.clone(), "GET", "/exists", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()
+                .get("x-custom")
+                .expect("header")
+                .to_str()
+                .expect("str"),
+            "layered"
+        );
+
+        // Fallback should also have the header.
+        let resp = send_request(app, "GET", "/not-here", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            resp.headers()
+                .get("x-custom")
+                .expect("header")
+                .to_str()
+                .expect("str"),
+            "layered"
+        );
+    }
+
+    #[tokio::test]
+    async fn layer_matching_applies_regardless_of_order() {
+        use tower_http::set_header::SetResponseHeaderLayer;
+
+        let admin_header = |resp: &axum::response::Response| {
+            resp.headers()
+                .get("x-admin")
+                .map(|value| value.to_str().expect("str").to_owned())
+        };
+
+        let merged = Router::new().route("/admin/audit", get(|| async { "audit" }));
+        let nested = Router::new().route("/users/{id}", get(|| async { "user" }));
+
+        let app = Router::new()
+            .route("/admin", get(|| async { "before" }))
+            .layer_matching(
+                "/admin/**",
+                SetResponseHeaderLayer::overriding(
+                    http::header::HeaderName::from_static("x-admin"),
+                    http::HeaderValue::from_static("yes"),
+                ),
+            )
+            .route("/admin/settings", get(|| async { "after" }))
+            .route("/public", get(|| async { "public" }))
+            .merge(merged)
+            .nest("/admin/v1", nested);
+
+        for uri in [
+            "/admin",
+            "/admin/settings",
+            "/admin/audit",
+            "/admin/v1/users/7",
+        ] {
+            let resp = send_request(app.clone(), "GET", uri, None).await;
+            assert_eq!(resp.status(), StatusCode::OK, "{uri}");
+            assert_eq!(admin_header(&resp).as_deref(), Some("yes"), "{uri}");
+        }
+
+        for uri in ["/public", "/administrator"] {
+            let resp = send_request(app.clone(), "GET", uri, None).await;
+            assert_eq!(admin_header(&resp), None, "{uri}");
+        }
+    }
+
+    // ==============================================================================
+    // Merge (additional)
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn merge_routers_with_overlapping_methods() {
+        // Two routers register different methods on the same path. After merge,
+        // both methods should work.
+        let router_get = Router::new().route("/item", get(|| async { "get" }));
+        let router_post = Router::new().route("/item", post(|| async { "post" }));
+
+        let app = router_get.merge(router_post);
+
+        let resp = send_request(app.clone(), "GET", "/item", None).await;
+        assert_eq!(get_body(resp).await, "get");
+
+        let resp = send_request(app, "POST", "/item", None).await;
+        assert_eq!(get_body(resp).await, "post");
+    }
+
+    // ==============================================================================
+    // Nesting
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn nest_basic_dispatch() {
+        let api = Router::new()
+            .route("/users", get(|| async { "users" }))
+            .route("/posts", get(|| async { "posts" }));
+
+        let app = Router::new().nest("/api", api);
+
+        let resp = send_request(app.clone(), "GET", "/api/users", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "users");
+
+        let resp = send_request(app.clone(), "GET", "/api/posts", None).await;
+        assert_eq!(get_body(resp).await, "posts");
+
+        // Non-nested path should 404.
+        let resp = send_request(app, "GET", "/users", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn nest_path_extraction() {
+        let api = Router::new().route(
+            "/users/{id}",
+            get(|Path(id): Path<u32>| async move { format!("user {id}") }),
+        );
+
+        let app = Router::new().nest("/api", api);
+
+        let resp = send_request(app, "GET", "/api/users/42", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "user 42");
+    }
+
+    #[tokio::test]
+    async fn nest_matched_path() {
+        let api = Router::new().route(
+            "/users/{id}",
+            get(|path: MatchedPath| async move { path.as_str().to_owned() }),
+        );
+
+        let app = Router::new().nest("/api", api);
+
+        let resp = send_request(app, "GET", "/api/users/42", None).await;
+        assert_eq!(get_body(resp).await, "/api/users/{id}");
+    }
+
+    #[tokio::test]
+    async fn nest_strips_prefix_from_uri() {
+        // Verify that handlers see the stripped URI path, not the full one.
+        let api = Router::new().route(
+            "/resource",
+            get(|req: axum::extract::Request| async move { req.uri().path().to_owned() }),
+        );
+
+        let app = Router::new().nest("/api/v1", api);
+
+        let resp = send_request(app, "GET", "/api/v1/resource", None).await;
+        assert_eq!(get_body(resp).await, "/resource");
+    }
+
+    #[tokio::test]
+    async fn nest_with_outer_routes() {
+        let api = Router::new().route("/items", get(|| async { "items" }));
+
+        let app = Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .nest("/api", api);
+
+        let resp = send_request(app.clone(), "GET", "/health", None).await;
+        assert_eq!(get_body(resp).await, "ok");
+
+        let resp = send_request(app, "GET", "/api/items", None).await;
+        assert_eq!(get_body(resp).await, "items");
+    }
+
+    #[tokio::test]
+    async fn nest_with_state() {
+        #[derive(Clone)]
+        struct AppState {
+            prefix: String,
+        }
+
+        let api =
+            Router::new().route(
+                "/greet",
+                get(|State(state): State<AppState>| async move {
+                    format!("hello from {}", state.prefix)
+                }),
+            );
+
+        let app = Router::new().nest("/api", api).with_state(AppState {
+            prefix: "api".to_owned(),
+        });
+
+        let resp = send_request(app, "GET", "/api/greet", None).await;
+        assert_eq!(get_body(resp).await, "hello from api");
+    }
+
+    #[tokio::test]
+    async fn nest_inner_fallback() {
+        let api = Router::new()
+            .route("/known", get(|| async { "known" }))
+            .fallback(|| async { (StatusCode::IM_A_TEAPOT, "api fallback") });
+
+        let app = Router::new().nest("/api", api);
+
+        // Known inner route should work.
+        let resp = send_request(app.clone(), "GET", "/api/known", None).await;
+        assert_eq!(get_body(resp).await, "known");
+
+        // Unknown inner path should use the inner fallback.
+        let resp = send_request(app.clone(), "GET", "/api/unknown", None).await;
+        assert_eq!(resp.status(), StatusCode::IM_A_TEAPOT);
+        assert_eq!(get_body(resp).await, "api fallback");
+
+        // Path outside the nest should use the outer (default 404) fallback.
+        let resp = send_request(app, "GET", "/other", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn nest_service_basic() {
+        use tower::service_fn;
+
+        let svc = service_fn(|req: axum::extract::Request| async move {
+            Ok::<_, std::convert::Infallible>(axum::response::IntoResponse::into_response(format!(
+                "service saw: {}",
+                req.uri().path()
+            )))
+        });
+
+        let app = Router::new().nest_service("/api", svc);
+
+        let resp = send_request(app.clone(), "GET", "/api/foo/bar", None).await;
+        assert_eq!(get_body(resp).await, "service saw: /foo/bar");
+
+        // Exact prefix should also work.
+        let resp = send_request(app.clone(), "GET", "/api", None).await;
+        assert_eq!(get_body(resp).await, "service saw: /");
+
+        // Trailing slash should work.
+        let resp = send_request(app, "GET", "/api/", None).await;
+        assert_eq!(get_body(resp).await, "service saw: /");
+    }
+
+    #[tokio::test]
+    async fn nest_service_with_router() {
+        // Nest an axum-wayfind Router as a service under a prefix.
+        let inner = Router::new()
+            .route("/users", get(|| async { "users" }))
+            .route(
+                "/users/{id}",
+                get(|Path(id): Path<u32>| async move { format!("user {id}") }),
+            );
+
+        let app = Router::new().nest_service("/api", inner);
+
+        let resp = send_request(app.clone(), "GET", "/api/users", None).await;
+        assert_eq!(get_body(resp).await, "users");
+
+        let resp = send_request(app, "GET", "/api/users/7", None).await;
+        assert_eq!(get_body(resp).await, "user 7");
+    }
+
+    #[tokio::test]
+    async fn nest_inner_root_route() {
+        // An inner router with route("/", ...) should be reachable at the bare
+        // prefix (without trailing slash) as well as with a trailing slash.
+        let inner = Router::new()
+            .route("/", get(|| async { "root" }))
+            .route("/other", get(|| async { "other" }));
+
+        let app = Router::new().nest("/api", inner);
+
+        // Bare prefix should match the inner "/" route.
+        let resp = send_request(app.clone(), "GET", "/api", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "root");
+
+        // Trailing slash should also match.
+        let resp = send_request(app.clone(), "GET", "/api/", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "root");
+
+        // Both forms share one route, reported as the bare prefix.
+        let bare = app.at("/api").expect("bare prefix matches");
+        let slash = app.at("/api/").expect("trailing slash matches");
+        assert_eq!(bare.route_id(), slash.route_id());
+        assert_eq!(slash.template(), "/api");
+
+        // Other inner routes still work.
+        let resp = send_request(app, "GET", "/api/other", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "other");
+    }
+
+    #[tokio::test]
+    async fn nest_double_nesting() {
+        let inner = Router::new().route(
+            "/resource",
+            get(|req: axum::extract::Request| async move { req.uri().path().to_owned() }),
+        );
+
+        let mid = Router::new().nest("/v1", inner);
+        let app = Router::new().nest("/api", mid);
+
+        let resp = send_request(app.clone(), "GET", "/api/v1/resource", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        // Each nesting layer strips its prefix, so the handler sees "/resource".
+        assert_eq!(get_body(resp).await, "/resource");
+
+        // Paths outside the nesting should 404.
+        let resp = send_request(app.clone(), "GET", "/api/resource", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = send_request(app, "GET", "/v1/resource", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn nest_router_with_nested_fallback_and_service() {
+        let inner = Router::new()
+            .route("/known", get(|| async { "known" }))
+            .fallback(|| async { (StatusCode::IM_A_TEAPOT, "inner fallback") });
+        let files = Router::new().route("/{*file}", get(|| async { "file" }));
+
+        let mid = Router::new()
+            .nest("/v1", inner)
+            .nest_service("/files", files);
+        let app = Router::new().nest("/api", mid.clone()).merge(mid);
+
+        for prefix in ["/api", ""] {
+            let resp = send_request(app.clone(), "GET", &format!("{prefix}/v1/known"), None).await;
+            assert_eq!(get_body(resp).await, "known");
+
+            let resp = send_request(app.clone(), "GET", &format!("{prefix}/v1/nope"), None).await;
+            assert_eq!(resp.status(), StatusCode::IM_A_TEAPOT);
+
+            let resp = send_request(app.clone(), "GET", &format!("{prefix}/files/a/b"), None).await;
+            assert_eq!(get_body(resp).await, "file");
+        }
+    }
+
+    #[tokio::test]
+    async fn nest_dynamic_prefix() {
+        let inner = Router::new().route(
+            "/users",
+            get(|req: axum::extract::Request| async move { req.uri().path().to_owned() }),
+        );
+
+        let app = Router::new().nest("/{version}", inner);
+
+        let resp = send_request(app.clone(), "GET", "/v2/users", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "/users");
+
+        let resp = send_request(app, "GET", "/v3/users", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "/users");
+    }
+
+    #[tokio::test]
+    async fn nest_preserves_query_string() {
+        let inner = Router::new().route(
+            "/search",
+            get(|req: axum::extract::Request| async move {
+                req.uri()
+                    .path_and_query()
+                    .map_or_else(|| "no query".to_owned(), |pq| pq.to_string())
+            }),
+        );
+
+        let app = Router::new().nest("/api", inner);
+
+        let resp = send_request(app, "GET", "/api/search?q=hello&page=1", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_body(resp).await, "/search?q=hello&page=1");
+    }
+
+    #[test]
+    #[should_panic(expected = "nesting at the root is not supported")]
+    fn nest_at_root_panics() {
+        drop(Router::<()>::new().nest("/", Router::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "nesting at the root is not supported")]
+    fn nest_empty_path_panics() {
+        drop(Router::<()>::new().nest("", Router::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "nest path must not contain wildcards")]
+    fn nest_wildcard_path_panics() {
+        drop(Router::<()>::new().nest("/{*catch_all}", Router::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "parameter names starting with `__private_` are reserved")]
+    fn route_with_reserved_param_name_panics() {
+        drop(Router::<()>::new().route("/{__private_foo}", get(|| async {})));
+    }
+
+    // ==============================================================================
+    // Introspection
+    // ==============================================================================
+
+    #[test]
+    fn explain_lists_candidates_and_winner() {
+        let app: Router = Router::new()
+            .route("/files/{*path}", get(|| async {}))
+            .route("/files/{name}", get(|| async {}))
+            .route("/other", get(|| async {}));
+
+        let explanation = app.explain("/files/readme");
+        let matched = explanation.matched().expect("route should match");
+        assert_eq!(matched.template(), "/files/{name}");
+        assert_eq!(matched.wayfind_template(), "/files/<name>");
+        assert_eq!(matched.params(), [("name".to_owned(), "readme".to_owned())]);
+
+        let candidates: Vec<_> = explanation
+            .candidates()
+            .iter()
+            .map(|c| (c.template(), c.is_selected()))
+            .collect();
+        assert_eq!(
+            candidates,
+            [("/files/{*path}", false), ("/files/{name}", true)]
+        );
+
+        let report = explanation.to_string();
+        assert!(report.contains("matched: #1 /files/{name}"));
+    }
+
+    #[test]
+    fn explain_unmatched_path() {
+        let app: Router = Router::new().route("/exists", get(|| async {}));
+
+        let explanation = app.explain("/missing");
+        assert!(explanation.matched().is_none());
+        assert!(explanation.candidates().is_empty());
+        assert!(explanation.to_string().contains("matched: <fallback>"));
+    }
+
+    #[test]
+    fn display_annotates_tree_with_templates() {
+        let app: Router = Router::new()
+            .route("/users/{id}", get(|| async {}))
+            .route("/files/{*path}", get(|| async {}));
+
+        let dump = app.to_string();
+        assert!(dump.contains("#0 /users/{id} -> /users/<id>"));
+        assert!(dump.contains("#1 /files/{*path} -> /files/<*path>"));
+        assert!(dump.contains("fallback: default (404)"));
+
+        let debug = format!("{app:?}");
+        assert!(debug.contains(r#"0: "/users/{id}""#));
+    }
+
+    #[test]
+    fn at_resolves_without_dispatch() {
+        #[derive(Deserialize)]
+        struct Params {
+            user_id: u32,
+            slug: String,
+        }
+
+        let app: Router = Router::new()
+            .route(
+                "/users/{user_id}/posts/{slug}",
+                get(|| async {}).post(|| async {}),
+            )
+            .route_service(
+                "/svc",
+                tower::service_fn(|_req: axum::extract::Request| async {
+                    Ok::<_, std::convert::Infallible>(axum::response::Response::new(
+                        axum::body::Body::empty(),
+                    ))
+                }),
+            );
+
+        let found = app.at("/users/7/posts/hello%20world").expect("route");
+        assert_eq!(found.template(), "/users/{user_id}/posts/{slug}");
+        assert_eq!(found.param("slug"), Some("hello world"));
+
+        let params: Params = found.deserialize().expect("deserialize");
+        assert_eq!(params.user_id, 7);
+        assert_eq!(params.slug, "hello world");
+
+        assert_eq!(
+            found.allowed_methods(),
+            &AllowedMethods::Only(vec![
+                http::Method::GET,
+                http::Method::HEAD,
+                http::Method::POST
+            ])
+        );
+
+        let svc = app.at("/svc").expect("route");
+        assert_eq!(svc.allowed_methods(), &AllowedMethods::Any);
+
+        assert!(app.at("/users/7").is_none());
+    }
+
+    #[test]
+    fn at_reports_invalid_utf8_instead_of_panicking() {
+        let app: Router = Router::new().route("/{key}", get(|| async {}));
+
+        let found = app.at("/%ff").expect("route still matches");
+        assert_eq!(found.params()[0].raw(), "%ff");
+        assert_eq!(found.param("key"), None);
+        assert!(found.deserialize::<String>().is_err());
+    }
+
+    // ==============================================================================
+    // route_service
+    // ==============================================================================
+
+    #[tokio::test]
+    async fn route_service_any_method() {
+        use tower::service_fn;
+
+        let svc = service_fn(|_req: axum::extract::Request| async {
+            Ok::<_, std::convert::Infallible>(axum::response::IntoResponse::into_response(
+                "from service",
+            ))
+        });
+
+        let app = Router::new().route_service("/svc", svc);
+
+        let resp = send_request(app.clone(), "GET", "/svc", None).await;
+        assert_eq!(get_body(resp).await, "from service");
+
+        let resp = send_request(app, "POST", "/svc", None).await;
+        assert_eq!(get_body(resp).await, "from service");
+    }
+
+    // ==============================================================================
+    // Automatic OPTIONS
+    // ==============================================================================
+
+    fn allow(resp: &axum::response::Response) -> Option<&str> {
+        resp.headers()
+            .get(http::header::ALLOW)
+            .map(|value| value.to_str().expect("str"))
+    }
+
+    #[tokio::test]
+    async fn auto_options_answers_with_merged_methods() {
+        let users = Router::new().route("/users/{id}", axum::routing::put(|| async {}));
+        let api = Router::new().route("/items", get(|| async {}));
+
+        let app = Router::new()
+            .route("/users/{id}", get(|| async {}))
+            .route(
+                "/custom",
+                get(|| async {}).options(|| async { "custom options" }),
+            )
+            .merge(users)
+            .nest("/api", api)
+            .auto_options();
+
+        let resp = send_request(app.clone(), "OPTIONS", "/users/1", None).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert_eq!(allow(&resp), Some("GET,HEAD,PUT,OPTIONS"));
+
+        let resp = send_request(app.clone(), "OPTIONS", "/api/items", None).await;
+        assert_eq!(allow(&resp), Some("GET,HEAD,OPTIONS"));
+
+        // Explicit OPTIONS handlers still run.
+        let resp = send_request(app.clone(), "OPTIONS", "/custom", None).await;
+        assert_eq!(get_body(resp).await, "custom options");
+
+        // Unmatched paths still go to the fallback.
+        let resp = send_request(app.clone(), "OPTIONS", "/nope", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = send_request(app, "OPTIONS", "*", None).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert_eq!(allow(&resp), Some("GET,HEAD,PUT,OPTIONS"));
+    }
+
+    #[tokio::test]
+    async fn auto_options_methods_survive_layers_and_state() {
+        let app = Router::<u32>::new()
+            .route("/count", post(|| async {}))
+            .route("/count", axum::routing::delete(|| async {}))
+            .layer(tower::layer::layer_fn(|inner| inner))
+            .with_state(1)
+            .auto_options();
+
+        let resp = send_request(app, "OPTIONS", "/count", None).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert_eq!(allow(&resp), Some("POST,DELETE,OPTIONS"));
+    }
+
+    #[tokio::test]
+    async fn auto_options_stays_with_its_routes() {
+        let app = Router::new()
+            .route("/outer", get(|| async {}))
+            .nest(
+                "/inner",
+                Router::new()
+                    .route("/items", get(|| async {}))
+                    .auto_options(),
+            )
+            .merge(
+                Router::new()
+                    .route("/merged", post(|| async {}))
+                    .auto_options(),
+            );
+
+        let resp = send_request(app.clone(), "OPTIONS", "/inner/items", None).await;
+        assert_eq!(allow(&resp), Some("GET,HEAD,OPTIONS"));
 
-        let resp = send_request(app, "GET", "/api/greet", None).await;
-        assert_eq!(get_body(resp).await, "hello from api");
+        let resp = send_request(app.clone(), "OPTIONS", "/merged", None).await;
+        assert_eq!(allow(&resp), Some("POST,OPTIONS"));
+
+        let resp = send_request(app.clone(), "OPTIONS", "/outer", None).await;
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let resp = send_request(app, "OPTIONS", "*", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn nest_inner_fallback() {
-        let api = Router::new()
-            .route("/known", get(|| async { "known" }))
-            .fallback(|| async { (StatusCode::IM_A_TEAPOT, "api fallback") });
+    async fn options_is_405_without_auto_options() {
+        let app = Router::new().route("/users/{id}", get(|| async {}));
 
-        let app = Router::new().nest("/api", api);
+        let resp = send_request(app.clone(), "OPTIONS", "/users/1", None).await;
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
 
-        // Known inner route should work.
-        let resp = send_request(app.clone(), "GET", "/api/known", None).await;
-        assert_eq!(get_body(resp).await, "known");
+        let resp = send_request(app, "OPTIONS", "*", None).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
 
-        // Unknown inner path should use the inner fallback.
-        let resp = send_request(app.clone(), "GET", "/api/unknown", None).await;
-        assert_eq!(resp.status(), StatusCode::IM_A_TEAPOT);
-        assert_eq!(get_body(resp).await, "api fallback");
+    // ==============================================================================
+    // Route policies
+    // ==============================================================================
 
-        // Path outside the nest should use the outer (default 404) fallback.
-        let resp = send_request(app, "GET", "/other", None).await;
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    async fn slow() -> &'static str {
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        "slow"
     }
 
     #[tokio::test]
-    async fn nest_service_basic() {
-        use tower::service_fn;
+    async fn route_policy_times_out_matching_routes() {
+        use axum_wayfind::policy::RoutePolicy;
+
+        let reports = Router::new()
+            .route("/reports/{*path}", get(slow))
+            .route("/reports", get(slow))
+            .route_policy(
+                "/reports/{*path}",
+                RoutePolicy::new().timeout(std::time::Duration::from_millis(20)),
+            );
+        // The policy travels with the route through `nest` and `merge`.
+        let app = Router::new()
+            .route("/users/{id}", get(slow))
+            .nest("/v1", reports.clone())
+            .merge(reports);
 
-        let svc = service_fn(|req: axum::extract::Request| async move {
-            Ok::<_, std::convert::Infallible>(axum::response::IntoResponse::into_response(format!(
-                "service saw: {}",
-                req.uri().path()
-            )))
-        });
+        for uri in ["/reports/a/b", "/v1/reports/a/b"] {
+            let resp = send_request(app.clone(), "GET", uri, None).await;
+            assert_eq!(resp.status(), StatusCode::REQUEST_TIMEOUT, "{uri}");
+        }
+        for uri in ["/reports", "/users/1"] {
+            let resp = send_request(app.clone(), "GET", uri, None).await;
+            assert_eq!(get_body(resp).await, "slow", "{uri}");
+        }
+    }
 
-        let app = Router::new().nest_service("/api", svc);
+    #[tokio::test]
+    async fn route_policy_sheds_load_over_the_limit() {
+        use axum_wayfind::policy::RoutePolicy;
 
-        let resp = send_request(app.clone(), "GET", "/api/foo/bar", None).await;
-        assert_eq!(get_body(resp).await, "service saw: /foo/bar");
+        let app = Router::new()
+            .route("/admin/export", get(slow).post(slow))
+            .route("/admin/users/{id}", get(slow))
+            .route_policy(
+                "/admin/**",
+                RoutePolicy::new().concurrency_limit(1).load_shed(),
+            );
 
-        // Exact prefix should also work.
-        let resp = send_request(app.clone(), "GET", "/api", None).await;
-        assert_eq!(get_body(resp).await, "service saw: /");
+        let busy = async {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            (
+                // The limit is shared by the route's methods...
+                send_request(app.clone(), "POST", "/admin/export", None)
+                    .await
+                    .status(),
+                // ...but not with other routes.
+                send_request(app.clone(), "GET", "/admin/users/1", None)
+                    .await
+                    .status(),
+            )
+        };
+        let (first, (same_route, other_route)) = tokio::join!(
+            send_request(app.clone(), "GET", "/admin/export", None),
+            busy
+        );
 
-        // Trailing slash should work.
-        let resp = send_request(app, "GET", "/api/", None).await;
-        assert_eq!(get_body(resp).await, "service saw: /");
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(same_route, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(other_route, StatusCode::OK);
     }
 
     #[tokio::test]
-    async fn nest_service_with_router() {
-        // Nest an axum-wayfind Router as a service under a prefix.
-        let inner = Router::new()
-            .route("/users", get(|| async { "users" }))
+    async fn route_policy_timeout_excludes_waiting_for_a_slot() {
+        use axum_wayfind::policy::RoutePolicy;
+
+        let app = Router::new()
             .route(
-                "/users/{id}",
-                get(|Path(id): Path<u32>| async move { format!("user {id}") }),
+                "/export",
+                get(|| async {
+                    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+                    "done"
+                }),
+            )
+            .route_policy(
+                "/export",
+                RoutePolicy::new()
+                    .timeout(std::time::Duration::from_millis(200))
+                    .concurrency_limit(1),
             );
 
-        let app = Router::new().nest_service("/api", inner);
+        // The second request waits 150ms for the first, then runs for 150ms
+        // of its own: over the timeout in total, but not once it has a slot.
+        let (first, second) = tokio::join!(
+            send_request(app.clone(), "GET", "/export", None),
+            send_request(app, "GET", "/export", None)
+        );
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::OK);
+    }
 
-        let resp = send_request(app.clone(), "GET", "/api/users", None).await;
-        assert_eq!(get_body(resp).await, "users");
+    #[test]
+    #[should_panic(expected = "template pattern must start with `/`")]
+    fn route_policy_rejects_relative_pattern() {
+        drop(
+            Router::<()>::new().route_policy("admin/**", axum_wayfind::policy::RoutePolicy::new()),
+        );
+    }
 
-        let resp = send_request(app, "GET", "/api/users/7", None).await;
-        assert_eq!(get_body(resp).await, "user 7");
+    // ==============================================================================
+    // Metrics
+    // ==============================================================================
+
+    /// Each recorded route, method, status and count.
+    fn metric_counts(
+        metrics: &axum_wayfind::metrics::InMemoryMetrics,
+    ) -> Vec<(String, &'static str, Option<u16>, u64)> {
+        metrics
+            .snapshot()
+            .iter()
+            .map(|entry| {
+                assert!(entry.latency_max() <= entry.latency_total());
+                (
+                    entry.route().to_owned(),
+                    entry.method(),
+                    entry.status().as_ref().map(StatusCode::as_u16),
+                    entry.count(),
+                )
+            })
+            .collect()
     }
 
     #[tokio::test]
-    async fn nest_inner_root_route() {
-        // An inner router with route("/", ...) should be reachable at the bare
-        // prefix (without trailing slash) as well as with a trailing slash.
-        let inner = Router::new()
-            .route("/", get(|| async { "root" }))
-            .route("/other", get(|| async { "other" }));
+    async fn metrics_are_labelled_by_template() {
+        use axum_wayfind::metrics::{InMemoryMetrics, OTHER_METHOD, UNMATCHED};
 
-        let app = Router::new().nest("/api", inner);
+        let metrics = InMemoryMetrics::new();
+        let scrape = metrics.clone();
 
-        // Bare prefix should match the inner "/" route.
-        let resp = send_request(app.clone(), "GET", "/api", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "root");
+        let api = Router::new()
+            .route("/items", get(|| async { "items" }))
+            .fallback(|| async { StatusCode::NOT_FOUND });
+        let app = Router::new()
+            .route(
+                "/users/{id}",
+                get(|Path(id): Path<u32>| async move { id.to_string() }),
+            )
+            .route("/metrics", get(move || async move { scrape.to_string() }))
+            .nest("/api", api)
+            .metrics_recorder(metrics.clone());
 
-        // Trailing slash should also match.
-        let resp = send_request(app.clone(), "GET", "/api/", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "root");
+        for uri in ["/users/1", "/users/2", "/users/abc", "/nope", "/api/nope"] {
+            send_request(app.clone(), "GET", uri, None).await;
+        }
+        // Made-up methods share one label.
+        for method in ["PURGE", "LOCK"] {
+            send_request(app.clone(), method, "/users/1", None).await;
+        }
 
-        // Other inner routes still work.
-        let resp = send_request(app, "GET", "/api/other", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "other");
+        assert_eq!(
+            metric_counts(&metrics),
+            vec![
+                ("/users/{id}".to_owned(), "GET", Some(200), 2),
+                ("/users/{id}".to_owned(), "GET", Some(400), 1),
+                ("/users/{id}".to_owned(), OTHER_METHOD, Some(405), 2),
+                (UNMATCHED.to_owned(), "GET", Some(404), 2),
+            ]
+        );
+
+        let resp = send_request(app, "GET", "/metrics", None).await;
+        let body = get_body(resp).await;
+        assert!(body.contains(
+            "http_server_requests_total{route=\"/users/{id}\",method=\"GET\",status=\"200\"} 2\n"
+        ));
+        assert!(body.contains(
+            "http_server_request_duration_seconds_count{route=\"unmatched\",method=\"GET\",status=\"404\"} 2\n"
+        ));
     }
 
     #[tokio::test]
-    async fn nest_double_nesting() {
-        let inner = Router::new().route(
-            "/resource",
-            get(|req: axum::extract::Request| async move { req.uri().path().to_owned() }),
-        );
-
-        let mid = Router::new().nest("/v1", inner);
-        let app = Router::new().nest("/api", mid);
+    async fn metrics_recorders_stay_with_their_routes() {
+        use axum_wayfind::metrics::InMemoryMetrics;
 
-        let resp = send_request(app.clone(), "GET", "/api/v1/resource", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        // Each nesting layer strips its prefix, so the handler sees "/resource".
-        assert_eq!(get_body(resp).await, "/resource");
+        let inner = InMemoryMetrics::new();
+        let app = Router::new()
+            .route("/outer", get(|| async {}))
+            .nest(
+                "/api",
+                Router::new()
+                    .route("/items", get(|| async {}))
+                    .metrics_recorder(inner.clone()),
+            );
 
-        // Paths outside the nesting should 404.
-        let resp = send_request(app.clone(), "GET", "/api/resource", None).await;
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        for uri in ["/outer", "/api/items", "/nope"] {
+            send_request(app.clone(), "GET", uri, None).await;
+        }
 
-        let resp = send_request(app, "GET", "/v1/resource", None).await;
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            metric_counts(&inner),
+            vec![("/api/items".to_owned(), "GET", Some(200), 1)]
+        );
     }
 
     #[tokio::test]
-    async fn nest_dynamic_prefix() {
-        let inner = Router::new().route(
-            "/users",
-            get(|req: axum::extract::Request| async move { req.uri().path().to_owned() }),
-        );
+    async fn dropped_requests_are_recorded_as_cancelled() {
+        use axum_wayfind::metrics::{CANCELLED, InMemoryMetrics};
 
-        let app = Router::new().nest("/{version}", inner);
+        let metrics = InMemoryMetrics::new();
+        let app = Router::new()
+            .route("/slow", get(slow))
+            .metrics_recorder(metrics.clone());
 
-        let resp = send_request(app.clone(), "GET", "/v2/users", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "/users");
+        let request = send_request(app, "GET", "/slow", None);
+        let timed_out = tokio::time::timeout(std::time::Duration::from_millis(20), request).await;
+        assert!(timed_out.is_err());
 
-        let resp = send_request(app, "GET", "/v3/users", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "/users");
+        assert_eq!(
+            metric_counts(&metrics),
+            vec![("/slow".to_owned(), "GET", None, 1)]
+        );
+        assert!(metrics.to_string().contains(&format!(
+            "http_server_requests_total{{route=\"/slow\",method=\"GET\",status=\"{CANCELLED}\"}} 1\n"
+        )));
     }
 
+    // ==============================================================================
+    // Redirects
+    // ==============================================================================
+
     #[tokio::test]
-    async fn nest_preserves_query_string() {
-        let inner = Router::new().route(
-            "/search",
-            get(|req: axum::extract::Request| async move {
-                req.uri()
-                    .path_and_query()
-                    .map_or_else(|| "no query".to_owned(), |pq| pq.to_string())
-            }),
+    async fn redirect_carries_params_and_query() {
+        let app = Router::new()
+            .route("/users/{id}", get(|| async { "user" }))
+            .redirect("/u/{id}", "/users/{id}", StatusCode::PERMANENT_REDIRECT)
+            .redirect(
+                "/files/{*path}",
+                "https://docs.example.com/{*path}",
+                StatusCode::MOVED_PERMANENTLY,
+            );
+
+        let resp = send_request(app.clone(), "POST", "/u/a%20b?tab=posts", None).await;
+        assert_eq!(resp.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            resp.headers().get(http::header::LOCATION),
+            Some(&http::HeaderValue::from_static("/users/a%20b?tab=posts"))
         );
 
-        let app = Router::new().nest("/api", inner);
+        let resp = send_request(app, "GET", "/files/guide/intro.md", None).await;
+        assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            resp.headers().get(http::header::LOCATION),
+            Some(&http::HeaderValue::from_static(
+                "https://docs.example.com/guide/intro.md"
+            ))
+        );
+    }
 
-        let resp = send_request(app, "GET", "/api/search?q=hello&page=1", None).await;
-        assert_eq!(resp.status(), StatusCode::OK);
-        assert_eq!(get_body(resp).await, "/search?q=hello&page=1");
+    #[tokio::test]
+    async fn redirect_captures_stay_on_the_same_host() {
+        let app = Router::new().redirect("/o/{*path}", "/{*path}", StatusCode::FOUND);
+
+        let resp = send_request(app, "GET", "/o//evil.com/x", None).await;
+        assert_eq!(resp.status(), StatusCode::FOUND);
+        assert_eq!(
+            resp.headers().get(http::header::LOCATION),
+            Some(&http::HeaderValue::from_static("/evil.com/x"))
+        );
     }
 
     #[test]
-    #[should_panic(expected = "nesting at the root is not supported")]
-    fn nest_at_root_panics() {
-        drop(Router::<()>::new().nest("/", Router::new()));
+    #[should_panic(expected = "which `/u/{id}` doesn't capture")]
+    fn redirect_rejects_uncaptured_target_param() {
+        let _app: Router = Router::new().redirect("/u/{id}", "/users/{user_id}", StatusCode::FOUND);
     }
 
-    #[test]
-    #[should_panic(expected = "nesting at the root is not supported")]
-    fn nest_empty_path_panics() {
-        drop(Router::<()>::new().nest("", Router::new()));
+    // ==============================================================================
+    // CORS
+    // ==============================================================================
+
+    #[cfg(feature = "cors")]
+    async fn send_cors(
+        app: Router,
+        method: &str,
+        uri: &str,
+        headers: &[(&str, &str)],
+    ) -> axum::response::Response {
+        let mut builder = http::Request::builder().method(method).uri(uri);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        let req = builder
+            .body(axum::body::Body::empty())
+            .expect("valid request");
+        app.oneshot(req).await.expect("infallible")
     }
 
-    #[test]
-    #[should_panic(expected = "nest path must not contain wildcards")]
-    fn nest_wildcard_path_panics() {
-        drop(Router::<()>::new().nest("/{*catch_all}", Router::new()));
+    /// Send a CORS preflight for `method` from `origin`.
+    #[cfg(feature = "cors")]
+    async fn send_preflight(
+        app: Router,
+        uri: &str,
+        origin: &str,
+        method: &str,
+    ) -> axum::response::Response {
+        send_cors(
+            app,
+            "OPTIONS",
+            uri,
+            &[
+                ("origin", origin),
+                ("access-control-request-method", method),
+            ],
+        )
+        .await
     }
 
-    #[test]
-    #[should_panic(expected = "parameter names starting with `__private_` are reserved")]
-    fn route_with_reserved_param_name_panics() {
-        drop(Router::<()>::new().route("/{__private_foo}", get(|| async {})));
+    #[cfg(feature = "cors")]
+    fn header<'a>(resp: &'a axum::response::Response, name: &str) -> Option<&'a str> {
+        resp.headers()
+            .get(name)
+            .map(|value| value.to_str().expect("str"))
+    }
+
+    #[cfg(feature = "cors")]
+    #[tokio::test]
+    async fn cors_preflight_allows_the_route_methods() {
+        use axum_wayfind::cors::CorsPolicy;
+        use http::HeaderValue;
+
+        const ORIGIN: &str = "https://app.example.com";
+
+        let users = Router::new().route("/users/{id}", axum::routing::put(|| async {}));
+        let app = Router::new()
+            .route("/users/{id}", get(|| async { "user" }))
+            .merge(users)
+            .cors(
+                CorsPolicy::new()
+                    .allow_origin(HeaderValue::from_static(ORIGIN))
+                    .allow_headers([http::header::CONTENT_TYPE]),
+            );
+
+        let resp = send_preflight(app.clone(), "/users/1", ORIGIN, "PUT").await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert_eq!(header(&resp, "access-control-allow-origin"), Some(ORIGIN));
+        assert_eq!(
+            header(&resp, "access-control-allow-methods"),
+            Some("GET,HEAD,PUT")
+        );
+        assert_eq!(
+            header(&resp, "access-control-allow-headers"),
+            Some("content-type")
+        );
+
+        // DELETE isn't registered, so the preflight grants nothing.
+        let resp = send_preflight(app.clone(), "/users/1", ORIGIN, "DELETE").await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert_eq!(header(&resp, "access-control-allow-origin"), None);
+
+        // Actual requests get the origin headers added.
+        let resp = send_cors(app.clone(), "GET", "/users/1", &[("origin", ORIGIN)]).await;
+        assert_eq!(header(&resp, "access-control-allow-origin"), Some(ORIGIN));
+        assert_eq!(header(&resp, "vary"), Some("origin"));
+        assert_eq!(get_body(resp).await, "user");
+
+        let resp = send_cors(
+            app,
+            "GET",
+            "/users/1",
+            &[("origin", "https://evil.example.com")],
+        )
+        .await;
+        assert_eq!(header(&resp, "access-control-allow-origin"), None);
+    }
+
+    #[cfg(feature = "cors")]
+    #[tokio::test]
+    async fn route_cors_overrides_the_router_policy_through_nest() {
+        use axum_wayfind::cors::CorsPolicy;
+        use http::HeaderValue;
+
+        const ADMIN: &str = "https://admin.example.com";
+
+        let admin = Router::new().route("/users", post(|| async {})).route_cors(
+            "/users",
+            CorsPolicy::new()
+                .allow_origin(HeaderValue::from_static(ADMIN))
+                .allow_credentials(),
+        );
+        let app = Router::new()
+            .route("/public", get(|| async {}))
+            .nest("/admin", admin)
+            .cors(CorsPolicy::new().allow_any_origin());
+
+        let resp = send_preflight(app.clone(), "/admin/users", ADMIN, "POST").await;
+        assert_eq!(header(&resp, "access-control-allow-origin"), Some(ADMIN));
+        assert_eq!(
+            header(&resp, "access-control-allow-credentials"),
+            Some("true")
+        );
+        assert_eq!(header(&resp, "access-control-allow-methods"), Some("POST"));
+
+        let resp = send_preflight(
+            app.clone(),
+            "/admin/users",
+            "https://other.example.com",
+            "POST",
+        )
+        .await;
+        assert_eq!(header(&resp, "access-control-allow-origin"), None);
+
+        // `/public` only has GET and HEAD.
+        let resp = send_preflight(app.clone(), "/public", "https://other.example.com", "GET").await;
+        assert_eq!(header(&resp, "access-control-allow-origin"), Some("*"));
+        assert_eq!(
+            header(&resp, "access-control-allow-methods"),
+            Some("GET,HEAD")
+        );
+
+        // Unmatched paths are left to the fallback.
+        let resp = send_preflight(app, "/nope", ADMIN, "POST").await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
     // ==============================================================================
-    // route_service
+    // Tracing
     // ==============================================================================
 
+    #[cfg(feature = "tracing")]
     #[tokio::test]
-    async fn route_service_any_method() {
-        use tower::service_fn;
+    async fn dispatch_opens_a_route_span() {
+        use std::{
+            collections::BTreeMap,
+            sync::{Arc, Mutex},
+        };
+        use tracing_subscriber::layer::SubscriberExt as _;
 
-        let svc = service_fn(|_req: axum::extract::Request| async {
-            Ok::<_, std::convert::Infallible>(axum::response::IntoResponse::into_response(
-                "from service",
-            ))
-        });
+        type Fields = BTreeMap<String, String>;
 
-        let app = Router::new().route_service("/svc", svc);
+        /// Records the fields of every `route` span.
+        #[derive(Clone, Default)]
+        struct RouteSpans(Arc<Mutex<Vec<Fields>>>);
 
-        let resp = send_request(app.clone(), "GET", "/svc", None).await;
-        assert_eq!(get_body(resp).await, "from service");
+        struct Visitor<'a>(&'a mut Fields);
 
-        let resp = send_request(app, "POST", "/svc", None).await;
-        assert_eq!(get_body(resp).await, "from service");
+        impl tracing::field::Visit for Visitor<'_> {
+            fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+                self.0.insert(field.name().to_owned(), value.to_owned());
+            }
+
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                self.0.insert(field.name().to_owned(), format!("{value:?}"));
+            }
+        }
+
+        impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for RouteSpans {
+            fn on_new_span(
+                &self,
+                attrs: &tracing::span::Attributes<'_>,
+                _id: &tracing::span::Id,
+                _ctx: tracing_subscriber::layer::Context<'_, S>,
+            ) {
+                if attrs.metadata().name() == "route" {
+                    let mut fields = Fields::new();
+                    attrs.record(&mut Visitor(&mut fields));
+                    self.0.lock().expect("lock").push(fields);
+                }
+            }
+
+            fn on_record(
+                &self,
+                _id: &tracing::span::Id,
+                values: &tracing::span::Record<'_>,
+                _ctx: tracing_subscriber::layer::Context<'_, S>,
+            ) {
+                if let Some(fields) = self.0.lock().expect("lock").last_mut() {
+                    values.record(&mut Visitor(fields));
+                }
+            }
+        }
+
+        let spans = RouteSpans::default();
+        let _subscriber =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(spans.clone()));
+
+        let api = Router::new()
+            .route("/items", get(|| async { "items" }))
+            .fallback(|| async { (StatusCode::NOT_FOUND, "api fallback") });
+        let app = Router::new()
+            .route("/users/{id}", get(|| async { "user" }))
+            .nest("/api", api);
+
+        for uri in ["/users/42", "/api/nope", "/nope"] {
+            send_request(app.clone(), "GET", uri, None).await;
+        }
+        send_request(app.clone(), "PURGE", "/users/42", None).await;
+
+        let fields = |pairs: &[(&str, &str)]| -> Fields {
+            pairs
+                .iter()
+                .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+                .collect()
+        };
+        assert_eq!(
+            *spans.0.lock().expect("lock"),
+            vec![
+                fields(&[
+                    ("otel.name", "GET /users/{id}"),
+                    ("http.request.method", "GET"),
+                    ("http.route", "/users/{id}"),
+                    ("route.id", "0"),
+                    ("route.handler", "route"),
+                ]),
+                fields(&[
+                    ("otel.name", "GET /api/{*__private_nest_tail}"),
+                    ("http.request.method", "GET"),
+                    ("http.route", "/api/{*__private_nest_tail}"),
+                    ("route.id", "2"),
+                    ("route.handler", "nested_fallback"),
+                ]),
+                fields(&[
+                    ("otel.name", "GET"),
+                    ("http.request.method", "GET"),
+                    ("route.handler", "fallback"),
+                ]),
+                fields(&[
+                    ("otel.name", "HTTP /users/{id}"),
+                    ("http.request.method", "_OTHER"),
+                    ("http.request.method_original", "PURGE"),
+                    ("http.route", "/users/{id}"),
+                    ("route.id", "0"),
+                    ("route.handler", "route"),
+                ]),
+            ]
+        );
     }
 }